@@ -58,19 +58,21 @@ router.post('/save', async (req: Request, res: Response) => {
             } as ApiResponse);
         }
 
-        // Fetch the recovery ID from the smart contract to ensure consistency
-        console.log('Fetching recovery ID from contract for target account:', targetAccount);
-        const contractRecoveryId = await casperService.getActiveRecoveryIdFromContract(targetAccount);
-        
-        // Use contract recovery ID if available, otherwise fall back to provided ID
+        // Check the recovery ID against the account's open proposals in the contract
+        console.log('Fetching open recovery IDs from contract for target account:', targetAccount);
+        const contractRecoveryIds = await casperService.getActiveRecoveryIdsFromContract(targetAccount);
+
+        // Several proposals can compete: keep the provided ID if it is open, otherwise use the newest
         let finalRecoveryId: string;
-        if (contractRecoveryId) {
-            finalRecoveryId = contractRecoveryId;
-            console.log('Using contract recovery ID:', finalRecoveryId);
-            
-            // Log if there's a mismatch between provided and contract ID
-            if (recoveryId && recoveryId !== contractRecoveryId) {
-                console.warn(`Recovery ID mismatch: provided=${recoveryId}, contract=${contractRecoveryId}. Using contract value.`);
+        if (recoveryId && contractRecoveryIds.includes(String(recoveryId))) {
+            finalRecoveryId = String(recoveryId);
+            console.log('Using provided recovery ID:', finalRecoveryId);
+        } else if (contractRecoveryIds.length > 0) {
+            finalRecoveryId = contractRecoveryIds[contractRecoveryIds.length - 1];
+            console.log('Using newest contract recovery ID:', finalRecoveryId);
+
+            if (recoveryId) {
+                console.warn(`Recovery ID ${recoveryId} is not open in the contract (open: ${contractRecoveryIds.join(', ')}). Using ${finalRecoveryId}.`);
             }
         } else if (recoveryId) {
             // Fallback to provided ID if contract query fails
@@ -426,20 +426,22 @@ router.get('/status/:recoveryId', async (req: Request, res: Response) => {
 
 /**
  * GET /recovery/active-from-contract/:publicKey
- * Get active recovery ID from contract dictionary (off-chain query)
- * This queries the contract's "a{:?}" key directly
+ * Get open recovery IDs from contract dictionary (off-chain query)
+ * This queries the contract's "ap{:?}" list directly; recoveryId is the newest
  */
 router.get('/active-from-contract/:publicKey', async (req: Request, res: Response) => {
     try {
         const { publicKey } = req.params;
 
-        const recoveryId = await casperService.getActiveRecoveryIdFromContract(publicKey);
+        const recoveryIds = await casperService.getActiveRecoveryIdsFromContract(publicKey);
+        const recoveryId = recoveryIds.length > 0 ? recoveryIds[recoveryIds.length - 1] : null;
 
         res.json({
             success: true,
             data: {
                 hasActiveRecovery: recoveryId !== null,
                 recoveryId: recoveryId,
+                recoveryIds: recoveryIds,
             },
         } as ApiResponse);
     } catch (error) {
@@ -239,9 +239,13 @@ export class CasperService {
 
             return {
                 isInitialized,
-                guardians: Array.isArray(guardians) ? guardians.map((g: any) =>
-                    typeof g === 'string' ? g : Buffer.from(g).toString('hex')
-                ) : [],
+                // Entries are account hashes, or Keys (account / contract package) for newer registrations
+                guardians: Array.isArray(guardians) ? guardians.map((g: any) => {
+                    while (g && typeof g === 'object' && !(g instanceof Uint8Array) && g.data !== undefined) {
+                        g = g.data;
+                    }
+                    return typeof g === 'string' ? g : Buffer.from(g).toString('hex');
+                }) : [],
                 threshold: Number(threshold),
             };
         } catch (error) {
@@ -574,15 +578,16 @@ export class CasperService {
     }
 
     /**
-     * Get active recovery ID for an account by querying the contract dictionary
-     * The contract stores active recovery IDs at key "a{:?}" where {:?} is AccountHash Debug format
+     * Get the open recovery IDs for an account by querying the contract dictionary
+     * The contract lists an account's open (competing) proposals at key "ap{:?}" where {:?}
+     * is AccountHash Debug format, oldest first; `get_recoveries_for_account` returns the same list
      */
-    async getActiveRecoveryIdFromContract(targetAccountHex: string): Promise<string | null> {
+    async getActiveRecoveryIdsFromContract(targetAccountHex: string): Promise<string[]> {
         try {
             const contractHash = config.contract.recoveryRegistryHash;
             if (!contractHash) {
                 console.error('Contract hash not configured');
-                return null;
+                return [];
             }
 
             const publicKey = CLPublicKey.fromHex(targetAccountHex);
@@ -590,59 +595,43 @@ export class CasperService {
             const accountHashHex = Buffer.from(accountHash).toString('hex');
             const debugFormat = `AccountHash(${accountHashHex})`;
 
-            console.log('\n=== Querying Active Recovery ID ===');
+            console.log('\n=== Querying Open Recovery IDs ===');
             console.log('Target Account:', targetAccountHex);
             console.log('Account Hash Debug Format:', debugFormat);
 
-            // The contract stores active recovery ID at "a{:?}" key
-            const activeKey = `a${debugFormat}`;
-            const result = await this.queryContractDictionary(contractHash, 'd', activeKey);
+            const openKey = `ap${debugFormat}`;
+            const result = await this.queryContractDictionary(contractHash, 'd', openKey);
 
-            if (result?.stored_value?.CLValue?.data !== undefined) {
-                const data = result.stored_value.CLValue.data;
-                // Handle BigNumber/U256 values
-                let recoveryId: string;
-                if (typeof data === 'object' && data !== null) {
-                    if (data._isBigNumber || typeof data.toString === 'function') {
-                        recoveryId = data.toString();
-                    } else {
-                        recoveryId = String(data);
-                    }
-                } else {
-                    recoveryId = String(data);
-                }
-                console.log('Active Recovery ID found:', recoveryId);
-                console.log('========================================\n');
-                return recoveryId;
-            }
+            // Handle the direct and wrapped CLValue response formats
+            const clValue = result?.CLValue ?? result?.stored_value?.CLValue;
+            const data = clValue?.data ?? clValue;
+            const ids: any[] = Array.isArray(data) ? data : [];
 
-            // Also check for CLValue directly (different SDK response format)
-            if (result?.CLValue?.data !== undefined) {
-                const data = result.CLValue.data;
-                let recoveryId: string;
-                if (typeof data === 'object' && data !== null) {
-                    if (data._isBigNumber || typeof data.toString === 'function') {
-                        recoveryId = data.toString();
-                    } else {
-                        recoveryId = String(data);
-                    }
-                } else {
-                    recoveryId = String(data);
+            // Parse recovery IDs from U256 values (which may be BigNumber objects)
+            const recoveryIds = ids.map((id: any) => {
+                if (typeof id === 'object' && id !== null && id.data !== undefined) {
+                    return String(id.data);
                 }
-                console.log('Active Recovery ID found (CLValue format):', recoveryId);
-                console.log('========================================\n');
-                return recoveryId;
-            }
+                return id.toString();
+            });
 
-            console.log('No active recovery found for this account');
+            console.log('Open recovery IDs:', recoveryIds);
             console.log('========================================\n');
-            return null;
+            return recoveryIds;
         } catch (error) {
-            console.error(`Error getting active recovery ID from contract: ${error}`);
-            return null;
+            console.error(`Error getting open recovery IDs from contract: ${error}`);
+            return [];
         }
     }
 
+    /**
+     * Get the newest open recovery ID for an account, see getActiveRecoveryIdsFromContract
+     */
+    async getActiveRecoveryIdFromContract(targetAccountHex: string): Promise<string | null> {
+        const recoveryIds = await this.getActiveRecoveryIdsFromContract(targetAccountHex);
+        return recoveryIds.length > 0 ? recoveryIds[recoveryIds.length - 1] : null;
+    }
+
     /**
      * Get active recovery ID for an account
      */
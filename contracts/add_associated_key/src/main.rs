@@ -11,17 +11,12 @@ use add_associated_key::errors::UserError;
 use casper_contract::contract_api::{account, runtime};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 use casper_types::account::Weight;
-use casper_types::Key;
+use guardian_types::account_arg;
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let new_associated_key: Key = runtime::get_named_arg(RUNTIME_ARG_NEW_ASSOCIATED_KEY);
+    let account = account_arg(RUNTIME_ARG_NEW_ASSOCIATED_KEY, UserError::InvalidAccount);
+    let new_key_weight: u8 = runtime::get_named_arg(RUNTIME_ARG_NEW_ASSOCIATED_KEY_WEIGHT);
 
-    if let Key::Account(account) = new_associated_key {
-        let new_key_weight: u8 = runtime::get_named_arg(RUNTIME_ARG_NEW_ASSOCIATED_KEY_WEIGHT);
-
-        account::add_associated_key(account, Weight::new(new_key_weight)).unwrap_or_revert();
-    } else {
-        runtime::revert(UserError::InvalidAccount);
-    }
+    account::add_associated_key(account, Weight::new(new_key_weight)).unwrap_or_revert();
 }
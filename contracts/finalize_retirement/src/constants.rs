@@ -0,0 +1,5 @@
+pub const RUNTIME_ARG_REGISTRY: &str = "registry";
+pub const RUNTIME_ARG_RECOVERY_ID: &str = "recovery_id";
+
+pub const REGISTRY_ENTRY_POINT_FINALIZE_RETIREMENT: &str = "finalize_retirement";
+pub const REGISTRY_ARG_ID: &str = "id";
@@ -0,0 +1,41 @@
+//! Finalize Retirement Contract
+//!
+//! Removes a key that `recovery_key_rotation` soft-retired (weight 0) once the
+//! grace period recorded in recovery_registry is over. The registry reverts the
+//! deploy if the grace period is still running or the recovery was rolled back.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+use casper_contract::contract_api::{account, runtime};
+use casper_types::account::{AccountHash, RemoveKeyFailure};
+use casper_types::{runtime_args, Key, U256};
+use guardian_types::registry_contract_hash;
+use finalize_retirement::constants::{
+    REGISTRY_ARG_ID, REGISTRY_ENTRY_POINT_FINALIZE_RETIREMENT, RUNTIME_ARG_RECOVERY_ID,
+    RUNTIME_ARG_REGISTRY,
+};
+use finalize_retirement::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let recovery_id: U256 = runtime::get_named_arg(RUNTIME_ARG_RECOVERY_ID);
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    // The registry checks the grace period and returns the retired key
+    let old_key: AccountHash = runtime::call_contract(
+        registry_hash,
+        REGISTRY_ENTRY_POINT_FINALIZE_RETIREMENT,
+        runtime_args! { REGISTRY_ARG_ID => recovery_id },
+    );
+
+    match account::remove_associated_key(old_key) {
+        Ok(()) | Err(RemoveKeyFailure::MissingKey) => {}
+        Err(_) => runtime::revert(UserError::RemoveKeyFailed),
+    }
+}
@@ -0,0 +1,9 @@
+pub const RUNTIME_ARG_REGISTRY: &str = "registry";
+pub const RUNTIME_ARG_DEPLOYMENT_THRESHOLD: &str = "deployment_threshold";
+pub const RUNTIME_ARG_KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
+pub const RUNTIME_ARG_UNFREEZE: &str = "unfreeze";
+
+pub const REGISTRY_ENTRY_POINT_GET_FREEZE: &str = "get_freeze";
+pub const REGISTRY_ENTRY_POINT_UNFREEZE: &str = "unfreeze";
+pub const REGISTRY_ENTRY_POINT_GET_KEY_CONFIG: &str = "get_key_config";
+pub const REGISTRY_ARG_ACCOUNT: &str = "account";
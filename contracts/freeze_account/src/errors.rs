@@ -0,0 +1,19 @@
+use casper_types::ApiError;
+
+#[repr(u16)]
+#[derive(Clone, Copy)]
+pub enum UserError {
+    InvalidRegistry,
+    NotFrozen,
+    UpdateThresholdsFailed,
+    /// The registry has no key configuration to compare the freeze thresholds with
+    NoKeyConfig,
+    /// A freeze must raise both thresholds above the owner key's weight and the recorded thresholds
+    ThresholdsNotRaised,
+}
+
+impl From<UserError> for ApiError {
+    fn from(error: UserError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
@@ -0,0 +1,86 @@
+//! Freeze Account Contract
+//!
+//! Emergency freeze of an account whose key was reported stolen, signed by its guardians:
+//! 1. Checks recovery_registry recorded a guardian freeze quorum for the account
+//! 2. Raises the deployment threshold above the stolen key's weight, so it can no
+//!    longer send deploys (and key management with it, which may never be lower).
+//!    Both must exceed the owner key's weight and the thresholds recorded in the
+//!    registry's key configuration.
+//!
+//! With `unfreeze = true` it is the matching way back: the registry only allows it
+//! once a recovery has been finalized since the freeze, and the thresholds are set
+//! back to the given values.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+use casper_contract::contract_api::runtime;
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{account::AccountHash, runtime_args, Key};
+use guardian_types::{freeze_thresholds_valid, registry_contract_hash, set_action_thresholds};
+use freeze_account::constants::{
+    REGISTRY_ARG_ACCOUNT, REGISTRY_ENTRY_POINT_GET_FREEZE, REGISTRY_ENTRY_POINT_GET_KEY_CONFIG,
+    REGISTRY_ENTRY_POINT_UNFREEZE,
+    RUNTIME_ARG_DEPLOYMENT_THRESHOLD, RUNTIME_ARG_KEY_MANAGEMENT_THRESHOLD, RUNTIME_ARG_REGISTRY,
+    RUNTIME_ARG_UNFREEZE,
+};
+use freeze_account::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // 1. Get runtime arguments
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let deployment_threshold: u8 = runtime::get_named_arg(RUNTIME_ARG_DEPLOYMENT_THRESHOLD);
+    let key_management_threshold: u8 =
+        runtime::get_named_arg(RUNTIME_ARG_KEY_MANAGEMENT_THRESHOLD);
+    let unfreeze: bool = runtime::try_get_named_arg(RUNTIME_ARG_UNFREEZE).unwrap_or(false);
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    let owner = runtime::get_caller();
+
+    // 2. Check the registry allows the change
+    if unfreeze {
+        // Reverts unless a recovery was finalized since the freeze
+        runtime::call_contract::<()>(
+            registry_hash,
+            REGISTRY_ENTRY_POINT_UNFREEZE,
+            runtime_args! { REGISTRY_ARG_ACCOUNT => owner },
+        );
+    } else {
+        let (frozen, _, _): (bool, u64, u8) = runtime::call_contract(
+            registry_hash,
+            REGISTRY_ENTRY_POINT_GET_FREEZE,
+            runtime_args! { REGISTRY_ARG_ACCOUNT => owner },
+        );
+        if !frozen {
+            runtime::revert(UserError::NotFrozen);
+        }
+
+        // A freeze that doesn't raise the thresholds leaves the stolen key working
+        let key_config: Option<(AccountHash, (u8, u8, u8))> = runtime::call_contract(
+            registry_hash,
+            REGISTRY_ENTRY_POINT_GET_KEY_CONFIG,
+            runtime_args! { REGISTRY_ARG_ACCOUNT => owner },
+        );
+        let (_, (owner_key_weight, current_deployment, current_key_management)) =
+            key_config.unwrap_or_revert_with(UserError::NoKeyConfig);
+        if !freeze_thresholds_valid(
+            owner_key_weight,
+            (current_deployment, current_key_management),
+            (deployment_threshold, key_management_threshold),
+        ) {
+            runtime::revert(UserError::ThresholdsNotRaised);
+        }
+    }
+
+    // 3. Apply the new thresholds
+    set_action_thresholds(
+        deployment_threshold,
+        key_management_threshold,
+        UserError::UpdateThresholdsFailed,
+    );
+}
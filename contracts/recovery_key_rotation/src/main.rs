@@ -1,11 +1,23 @@
 //! Recovery Key Rotation Contract
 //!
 //! This contract performs a complete account key rotation for recovery:
-//! 1. Adds a new associated key with specified weight
+//! 1. Adds the new associated key(s) with their weights
 //! 2. Updates thresholds to give the new key control
 //! 3. Removes the old (lost) associated key
 //!
 //! All operations happen in a single deploy, requiring multi-sig from guardians.
+//!
+//! With a `recovery_id` the rotation is recorded in recovery_registry, which checks
+//! the old key against the account's recorded key configuration and supplies the
+//! approved targets, so the signers cannot rotate to other keys.
+//!
+//! With `soft_retire` the old key is kept associated at weight 0 instead of being
+//! removed, and the registry keeps the pre-recovery configuration. The old key can
+//! then authorize a rollback during the registry's grace period, after which
+//! `finalize_retirement` removes the old key for good.
+//!
+//! Every rotation appends a `RecoveryReceipt` to the account's
+//! `sentinelx_recovery_receipts` named key so its recovery history stays on-chain.
 
 #![no_std]
 #![no_main]
@@ -15,28 +27,49 @@ compile_error!("target arch should be wasm32: compile with '--target wasm32-unkn
 
 extern crate alloc;
 
-use casper_contract::contract_api::{account, runtime};
+use alloc::vec;
+use alloc::vec::Vec;
+use casper_contract::contract_api::{account, runtime, storage};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use casper_types::account::{ActionType, Weight};
-use casper_types::{ApiError, Key};
+use casper_types::account::{AccountHash, RemoveKeyFailure, Weight};
+use casper_types::{runtime_args, ApiError, Key, PublicKey, U256};
+use guardian_types::{
+    account_arg, registry_contract_hash, set_action_thresholds, RecoveryReceipt,
+    RECOVERY_RECEIPTS_KEY,
+};
 
 // Runtime argument names
 const ARG_NEW_KEY: &str = "new_key";
+const ARG_NEW_KEYS: &str = "new_keys";
 const ARG_NEW_KEY_WEIGHT: &str = "new_key_weight";
 const ARG_OLD_KEY: &str = "old_key";
 const ARG_DEPLOYMENT_THRESHOLD: &str = "deployment_threshold";
 const ARG_KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
+const ARG_SOFT_RETIRE: &str = "soft_retire";
+const ARG_REGISTRY: &str = "registry";
+const ARG_RECOVERY_ID: &str = "recovery_id";
+
+// Registry entry point recording a rotation of a registry recovery
+const REGISTRY_RECORD_ROTATION: &str = "record_rotation";
 
 // Custom errors
 #[repr(u16)]
+#[derive(Clone, Copy)]
 enum RecoveryError {
     InvalidNewKey = 1,
     InvalidOldKey = 2,
     AddKeyFailed = 3,
     UpdateThresholdsFailed = 4,
     RemoveKeyFailed = 5,
+    InvalidRegistry = 6,
+    RetireKeyFailed = 7,
+    InvalidReceipts = 8,
 }
 
+/// Keys to add with their weights and the previous (deployment, key management) thresholds,
+/// as returned by the registry's `record_rotation`
+type RecordedRotation = (Vec<(AccountHash, u8)>, Option<(u8, u8)>);
+
 impl From<RecoveryError> for ApiError {
     fn from(error: RecoveryError) -> Self {
         ApiError::User(error as u16)
@@ -46,39 +79,124 @@ impl From<RecoveryError> for ApiError {
 #[no_mangle]
 pub extern "C" fn call() {
     // 1. Get runtime arguments
-    let new_key: Key = runtime::get_named_arg(ARG_NEW_KEY);
-    let new_key_weight: u8 = runtime::get_named_arg(ARG_NEW_KEY_WEIGHT);
-    let old_key: Key = runtime::get_named_arg(ARG_OLD_KEY);
+    // `new_keys` rotates to several keys at once; otherwise a single `new_key` is used
+    let new_keys: Option<Vec<(PublicKey, u8)>> = runtime::try_get_named_arg(ARG_NEW_KEYS);
     let deployment_threshold: u8 = runtime::get_named_arg(ARG_DEPLOYMENT_THRESHOLD);
     let key_management_threshold: u8 = runtime::get_named_arg(ARG_KEY_MANAGEMENT_THRESHOLD);
+    let soft_retire: bool = runtime::try_get_named_arg(ARG_SOFT_RETIRE).unwrap_or(false);
+    // Optional unless soft retiring; the registry then supplies the targets
+    let recovery_id: Option<U256> = runtime::try_get_named_arg(ARG_RECOVERY_ID);
 
-    // 2. Validate keys are account hashes
-    let new_account_hash = match new_key {
-        Key::Account(hash) => hash,
-        _ => runtime::revert(RecoveryError::InvalidNewKey),
-    };
+    // 2. Validate keys resolve to account hashes
+    let old_account_hash = account_arg(ARG_OLD_KEY, RecoveryError::InvalidOldKey);
 
-    let old_account_hash = match old_key {
-        Key::Account(hash) => hash,
-        _ => runtime::revert(RecoveryError::InvalidOldKey),
+    // The previous thresholds are only known from the registry's record of the account
+    let (new_account_hashes, previous_thresholds) = match recovery_id {
+        Some(id) => record_rotation(
+            id,
+            old_account_hash,
+            (deployment_threshold, key_management_threshold),
+            soft_retire,
+        ),
+        None if soft_retire => runtime::revert(ApiError::MissingArgument),
+        None => (new_key_args(new_keys), None),
     };
 
-    // Step 1: Add the new key with specified weight
-    // This gives the new key permission to participate in account operations
-    account::add_associated_key(new_account_hash, Weight::new(new_key_weight))
-        .unwrap_or_revert_with(RecoveryError::AddKeyFailed);
-
-    // Step 2: Update thresholds
-    // Lower the thresholds so the new key has control
-    // Do key management first, then deployment
-    account::set_action_threshold(ActionType::KeyManagement, Weight::new(key_management_threshold))
-        .unwrap_or_revert_with(RecoveryError::UpdateThresholdsFailed);
-    
-    account::set_action_threshold(ActionType::Deployment, Weight::new(deployment_threshold))
-        .unwrap_or_revert_with(RecoveryError::UpdateThresholdsFailed);
-
-    // Step 3: Remove the old key
-    // The old (lost) key is removed from the account
-    // Note: Using try pattern as removal might fail if key doesn't exist
-    let _ = account::remove_associated_key(old_account_hash);
+    // Step 1: Add the new key(s) with their weights
+    // This gives the new keys permission to participate in account operations
+    for (new_account_hash, weight) in new_account_hashes.iter() {
+        account::add_associated_key(*new_account_hash, Weight::new(*weight))
+            .unwrap_or_revert_with(RecoveryError::AddKeyFailed);
+    }
+
+    // Step 2: Update thresholds so the new keys have control, in whichever order
+    // keeps key management >= deployment
+    set_action_thresholds(
+        deployment_threshold,
+        key_management_threshold,
+        RecoveryError::UpdateThresholdsFailed,
+    );
+
+    // Step 3: Retire the old key
+    if soft_retire {
+        // Keep the old key associated with no weight; the registry tracks the
+        // grace period during which the owner can roll back
+        account::update_associated_key(old_account_hash, Weight::new(0))
+            .unwrap_or_revert_with(RecoveryError::RetireKeyFailed);
+    } else {
+        // The old (lost) key is removed from the account
+        // Note: A key that is already gone is not an error
+        match account::remove_associated_key(old_account_hash) {
+            Ok(()) | Err(RemoveKeyFailure::MissingKey) => {}
+            Err(_) => runtime::revert(RecoveryError::RemoveKeyFailed),
+        }
+    }
+
+    // Step 4: Leave a receipt of the rotation in the account
+    append_receipt(RecoveryReceipt {
+        recovery_id,
+        old_key: old_account_hash,
+        new_keys: new_account_hashes,
+        previous_thresholds,
+        new_thresholds: (deployment_threshold, key_management_threshold),
+        soft_retired: soft_retire,
+        block_time: runtime::get_blocktime().into(),
+    });
+}
+
+/// Keys to add when rotating without a registry recovery: `new_keys`, or `new_key` with its weight
+fn new_key_args(new_keys: Option<Vec<(PublicKey, u8)>>) -> Vec<(AccountHash, u8)> {
+    match new_keys {
+        Some(keys) if !keys.is_empty() => keys
+            .iter()
+            .map(|(key, weight)| (key.to_account_hash(), *weight))
+            .collect(),
+        Some(_) => runtime::revert(RecoveryError::InvalidNewKey),
+        None => {
+            let new_key = account_arg(ARG_NEW_KEY, RecoveryError::InvalidNewKey);
+            let new_key_weight: u8 = runtime::get_named_arg(ARG_NEW_KEY_WEIGHT);
+            vec![(new_key, new_key_weight)]
+        }
+    }
+}
+
+/// Append a receipt to the account's recovery history named key
+fn append_receipt(receipt: RecoveryReceipt) {
+    match runtime::get_key(RECOVERY_RECEIPTS_KEY) {
+        Some(key) => {
+            let uref = key
+                .into_uref()
+                .unwrap_or_revert_with(RecoveryError::InvalidReceipts);
+            let mut receipts: Vec<RecoveryReceipt> = storage::read(uref)
+                .unwrap_or_revert_with(RecoveryError::InvalidReceipts)
+                .unwrap_or_default();
+            receipts.push(receipt);
+            storage::write(uref, receipts);
+        }
+        None => runtime::put_key(RECOVERY_RECEIPTS_KEY, storage::new_uref(vec![receipt]).into()),
+    }
+}
+
+/// Record the rotation in recovery_registry, which returns the recovery's approved targets and
+/// the account's thresholds before the rotation
+fn record_rotation(
+    recovery_id: U256,
+    old_account_hash: AccountHash,
+    (deployment_threshold, key_management_threshold): (u8, u8),
+    soft_retire: bool,
+) -> RecordedRotation {
+    let registry: Key = runtime::get_named_arg(ARG_REGISTRY);
+    let registry_hash = registry_contract_hash(registry, RecoveryError::InvalidRegistry);
+
+    runtime::call_contract(
+        registry_hash,
+        REGISTRY_RECORD_ROTATION,
+        runtime_args! {
+            "id" => recovery_id,
+            "old_key" => old_account_hash,
+            "deployment_threshold" => deployment_threshold,
+            "key_management_threshold" => key_management_threshold,
+            "soft_retire" => soft_retire,
+        },
+    )
 }
@@ -0,0 +1,146 @@
+//! SentinelX - Recovery Receipt Query Helper
+//!
+//! Host-side decoding of the receipts `recovery_key_rotation.wasm` appends to a
+//! recovered account's `sentinelx_recovery_receipts` named key. Query the URef
+//! behind that named key from a node and pass the CLValue's `bytes` field here.
+//!
+//! The crate is `no_std` (with `alloc`) like guardian-types, so it also builds
+//! alongside the session WASMs in a wasm32 workspace build.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes},
+    U256,
+};
+
+pub use guardian_types::{RecoveryReceipt, RECOVERY_RECEIPTS_KEY};
+
+/// Errors decoding a receipt history
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input is not valid hex
+    InvalidHex,
+    /// The bytes are not a serialized receipt list
+    Bytesrepr(bytesrepr::Error),
+}
+
+impl From<bytesrepr::Error> for DecodeError {
+    fn from(error: bytesrepr::Error) -> Self {
+        DecodeError::Bytesrepr(error)
+    }
+}
+
+/// Decode a serialized `Vec<RecoveryReceipt>`, oldest receipt first
+pub fn decode_receipts(bytes: &[u8]) -> Result<Vec<RecoveryReceipt>, DecodeError> {
+    let (receipts, rem) = Vec::<RecoveryReceipt>::from_bytes(bytes)?;
+    if !rem.is_empty() {
+        return Err(bytesrepr::Error::LeftOverBytes.into());
+    }
+    Ok(receipts)
+}
+
+/// Decode the hex `bytes` field of a CLValue returned by the node RPC
+pub fn decode_receipts_hex(hex: &str) -> Result<Vec<RecoveryReceipt>, DecodeError> {
+    let hex = hex.trim().trim_start_matches("0x");
+    if hex.len() % 2 != 0 {
+        return Err(DecodeError::InvalidHex);
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| DecodeError::InvalidHex))
+        .collect::<Result<Vec<u8>, _>>()?;
+    decode_receipts(&bytes)
+}
+
+/// Receipts for one registry recovery id
+pub fn receipts_for_recovery(
+    receipts: &[RecoveryReceipt],
+    recovery_id: U256,
+) -> Vec<&RecoveryReceipt> {
+    receipts
+        .iter()
+        .filter(|receipt| receipt.recovery_id == Some(recovery_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{account::AccountHash, bytesrepr::ToBytes};
+    use std::fmt::Write;
+
+    fn receipt(recovery_id: Option<u64>) -> RecoveryReceipt {
+        RecoveryReceipt {
+            recovery_id: recovery_id.map(U256::from),
+            old_key: AccountHash::new([1; 32]),
+            new_keys: vec![(AccountHash::new([2; 32]), 3)],
+            previous_thresholds: Some((1, 1)),
+            new_thresholds: (3, 3),
+            soft_retired: false,
+            block_time: 42,
+        }
+    }
+
+    fn receipts() -> Vec<RecoveryReceipt> {
+        vec![receipt(Some(1)), receipt(None), receipt(Some(2)), receipt(Some(1))]
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut out, b| {
+            let _ = write!(out, "{:02x}", b);
+            out
+        })
+    }
+
+    #[test]
+    fn decodes_bytes() {
+        let bytes = receipts().to_bytes().unwrap();
+        assert_eq!(decode_receipts(&bytes).unwrap(), receipts());
+    }
+
+    #[test]
+    fn rejects_left_over_bytes() {
+        let mut bytes = receipts().to_bytes().unwrap();
+        bytes.push(0);
+        assert!(matches!(
+            decode_receipts(&bytes),
+            Err(DecodeError::Bytesrepr(bytesrepr::Error::LeftOverBytes))
+        ));
+    }
+
+    #[test]
+    fn decodes_rpc_hex() {
+        let hex = hex(&receipts().to_bytes().unwrap());
+        assert_eq!(decode_receipts_hex(&hex).unwrap(), receipts());
+        assert_eq!(decode_receipts_hex(&format!(" 0x{}\n", hex)).unwrap(), receipts());
+        assert_eq!(decode_receipts_hex(&hex.to_uppercase()).unwrap(), receipts());
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        let hex = hex(&receipts().to_bytes().unwrap());
+        assert!(matches!(decode_receipts_hex(&hex[1..]), Err(DecodeError::InvalidHex)));
+        assert!(matches!(decode_receipts_hex(&format!("zz{}", hex)), Err(DecodeError::InvalidHex)));
+        assert!(matches!(
+            decode_receipts_hex(&hex[..hex.len() - 2]),
+            Err(DecodeError::Bytesrepr(_))
+        ));
+    }
+
+    #[test]
+    fn empty_history() {
+        assert!(decode_receipts_hex("00000000").unwrap().is_empty());
+    }
+
+    #[test]
+    fn filters_by_recovery_id() {
+        let receipts = receipts();
+        let found = receipts_for_recovery(&receipts, U256::from(1));
+        assert_eq!(found, vec![&receipts[0], &receipts[3]]);
+        assert!(receipts_for_recovery(&receipts, U256::from(3)).is_empty());
+    }
+}
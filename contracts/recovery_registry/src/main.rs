@@ -4,26 +4,33 @@
 extern crate alloc;
 
 use alloc::{vec::Vec, vec, boxed::Box, format};
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
+use guardian_types::{groups_satisfied, groups_valid, migrated_proposals, thresholds_valid, ApprovalMessage, GuardianGroup, ProposalApproval, RollbackMessage};
 use casper_contract::{
-    contract_api::{runtime, storage},
+    contract_api::{cryptography, runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     account::AccountHash, 
-    contracts::{EntryPoint, EntryPoints},
+    contracts::{ContractHash, ContractPackageHash, EntryPoint, EntryPoints},
     EntryPointAccess,
     EntryPointType, 
     ApiError,
     Key, 
+    NamedKeys,
     Parameter,
+    PackageHash,
     PublicKey, 
+    Signature,
     U256,
+    U512,
     URef,
     CLTyped,
-    bytesrepr::{FromBytes, ToBytes},
+    bytesrepr::{Bytes, FromBytes, ToBytes},
     CLType,
     CLValue,
+    EntityAddr,
+    HashAddr,
 };
 
 #[repr(u16)]
@@ -33,15 +40,99 @@ enum Err {
     BadGuardians = 3,
     BadThreshold = 4,
     NotGuardian = 5,
+    /// No longer raised since accounts may have competing proposals; kept so codes stay stable
+    #[allow(dead_code)]
     RecoveryExists = 6,
     NotFound = 7,
     AlreadyApproved = 8,
     NotApproved = 9,
     NotInit = 10,
     MissingDict = 11,
+    BadTargets = 12,
+    NotRecoveryAccount = 13,
+    RetirementExists = 14,
+    NoRetirement = 15,
+    GracePeriodActive = 16,
+    GracePeriodOver = 17,
+    Closed = 18,
+    BadPolicy = 19,
+    NotInitiator = 20,
+    BondRequired = 21,
+    BadBond = 22,
+    NotExpired = 23,
+    Expired = 24,
+    AlreadyRejected = 25,
+    BadEscrow = 26,
+    RecoveryInProgress = 27,
+    NotNominated = 28,
+    AlreadyAccepted = 29,
+    NotCoSigned = 30,
+    BadSignature = 31,
+    NoChainName = 32,
+    BadCode = 33,
+    BadDelay = 34,
+    NoInheritance = 35,
+    OwnerActive = 36,
+    AlreadyFrozen = 37,
+    NotFrozen = 38,
+    NotRecovered = 39,
+    IsDrill = 40,
+    NoProposal = 41,
+    BadGroups = 42,
+    NoKeyConfig = 43,
+    WrongKey = 44,
+    RollbackNotAuthorized = 45,
+    ApprovedOther = 46,
+    QuorumUnreachable = 47,
 }
 
 const DICT: &str = "d";
+/// Named key of the registry-owned purse holding recovery bonds
+const BOND_PURSE: &str = "bond_purse";
+/// Named key of the registry-owned purse holding guardian reward escrows
+const ESCROW_PURSE: &str = "escrow_purse";
+/// Optional named key of the purse receiving forfeited bonds (the protected account otherwise)
+const TREASURY: &str = "treasury";
+/// Optional named key holding the network name that off-chain approvals are bound to
+const CHAIN_NAME: &str = "chain_name";
+/// Open proposals expire 30 days (in ms) after they are started
+const RECOVERY_EXPIRY: u64 = 30 * 24 * 60 * 60 * 1000;
+/// Default delay before a backup key's approval takes effect (30 days, in ms)
+const DEFAULT_BACKUP_DELAY: u64 = 30 * 24 * 60 * 60 * 1000;
+/// Shortest backup delay: the owner gets at least a full approval window to notice and cancel
+const MIN_BACKUP_DELAY: u64 = RECOVERY_EXPIRY;
+/// Weight given to a single `new_key` target
+const DEFAULT_KEY_WEIGHT: u8 = 3;
+/// A guardian counts as live for 90 days (in ms) after their last heartbeat
+const LIVENESS_WINDOW: u64 = 90 * 24 * 60 * 60 * 1000;
+/// Grace period before a soft-retired key can be removed (7 days, in ms)
+const DEFAULT_RETIREMENT_GRACE: u64 = 7 * 24 * 60 * 60 * 1000;
+/// A freeze vote lapses 7 days (in ms) after it is cast, so votes can't pile up over months
+const FREEZE_VOTE_TTL: u64 = 7 * 24 * 60 * 60 * 1000;
+
+// Who may call start_recovery for an account (anyone unless the owner chose otherwise)
+const POLICY_GUARDIANS: u8 = 0;
+const POLICY_INITIATOR: u8 = 1;
+const POLICY_BOND: u8 = 2;
+const POLICY_ANYONE: u8 = 3;
+
+// CallerInfo kinds (casper-types) for callers that are addressable entities or legacy contracts,
+// and the CallerInfo fields holding their package and entity or contract hashes
+const CALLER_ENTITY: u8 = 3;
+const CALLER_CONTRACT: u8 = 4;
+const FIELD_PACKAGE: u8 = 1;
+const FIELD_CONTRACT_PACKAGE: u8 = 2;
+const FIELD_ENTITY: u8 = 3;
+const FIELD_CONTRACT: u8 = 4;
+
+// Soft retirement status values
+const RETIREMENT_PENDING: u8 = 1;
+const RETIREMENT_DONE: u8 = 2;
+const RETIREMENT_ROLLED_BACK: u8 = 3;
+const RETIREMENT_ROLLBACK_AUTHORIZED: u8 = 4;
+
+/// An account's owner key and its (weight, deployment threshold, key management threshold)
+type KeyConfig = (AccountHash, (u8, u8, u8));
 
 fn get_dict() -> URef {
     runtime::get_key(DICT)
@@ -58,54 +149,620 @@ fn write<T: CLTyped + ToBytes>(k: &str, v: T) {
     storage::dictionary_put(get_dict(), k, v);
 }
 
+fn now() -> u64 {
+    runtime::get_blocktime().into()
+}
+
+/// Guardian identity of the immediate caller: the calling contract's package hash when the
+/// registry is called from another contract (e.g. a DAO or multisig), the calling account otherwise
+fn caller_key() -> Key {
+    let stack = runtime::get_call_stack();
+    if let Some(info) = stack.iter().rev().nth(1) {
+        let package = match info.kind() {
+            CALLER_CONTRACT => info.get_field_by_index(FIELD_CONTRACT_PACKAGE)
+                .and_then(|v| v.clone().into_t::<Option<ContractPackageHash>>().ok()).flatten()
+                .map(|p| p.value()),
+            CALLER_ENTITY => info.get_field_by_index(FIELD_PACKAGE)
+                .and_then(|v| v.clone().into_t::<Option<PackageHash>>().ok()).flatten()
+                .map(|p| p.value()),
+            _ => None,
+        };
+        if let Some(hash) = package { return Key::Hash(hash); }
+    }
+    Key::Account(runtime::get_caller())
+}
+
+/// Hash of this registry contract, the last entry on the call stack
+fn registry_hash() -> HashAddr {
+    let stack = runtime::get_call_stack();
+    let info = stack.last().unwrap_or_revert();
+    let hash = match info.kind() {
+        CALLER_CONTRACT => info.get_field_by_index(FIELD_CONTRACT)
+            .and_then(|v| v.clone().into_t::<Option<ContractHash>>().ok()).flatten()
+            .map(|h| h.value()),
+        CALLER_ENTITY => info.get_field_by_index(FIELD_ENTITY)
+            .and_then(|v| v.clone().into_t::<Option<EntityAddr>>().ok()).flatten()
+            .map(|a| a.value()),
+        _ => None,
+    };
+    hash.unwrap_or_revert()
+}
+
+/// Dictionary key fragment for a guardian. Account guardians keep the `AccountHash(..)` form
+/// used before contract guardians existed.
+fn gid(guard: &Key) -> String {
+    match guard.into_account() {
+        Some(a) => format!("{:?}", a),
+        None => guard.to_formatted_string(),
+    }
+}
+
+/// Guardian list stored under `k`; lists written before guardians were `Key`s hold account hashes
+fn read_guardians(k: &str) -> Option<Vec<Key>> {
+    read::<Vec<AccountHash>>(k)
+        .map(|g| g.into_iter().map(Key::Account).collect())
+        .or_else(|| read::<Vec<Key>>(k))
+}
+
+/// `guardian` argument given either as an account hash or as a `Key`
+fn guardian_arg() -> Key {
+    match runtime::try_get_named_arg::<AccountHash>("guardian") {
+        Some(a) => Key::Account(a),
+        None => runtime::get_named_arg("guardian"),
+    }
+}
+
+/// `guardians` argument: accounts or contract packages; a plain list of account hashes is still accepted
+fn guardian_list_arg() -> Vec<Key> {
+    let guards: Vec<Key> = match runtime::try_get_named_arg::<Vec<AccountHash>>("guardians") {
+        Some(g) => g.into_iter().map(Key::Account).collect(),
+        None => runtime::get_named_arg("guardians"),
+    };
+    if guards.iter().any(|g| !matches!(g, Key::Account(_) | Key::Hash(_))) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    guards
+}
+
+/// Per-action thresholds from the args, in the order recovery approval, rejection, guardian-set
+/// change and freeze. Those not given default to `threshold`.
+fn threshold_args(total: usize) -> Vec<u8> {
+    let thresh: u8 = runtime::get_named_arg("threshold");
+    let tj: u8 = runtime::try_get_named_arg("rejection_threshold").unwrap_or(thresh);
+    let tg: u8 = runtime::try_get_named_arg("guardian_change_threshold").unwrap_or(thresh);
+    let tf: u8 = runtime::try_get_named_arg("freeze_threshold").unwrap_or(thresh);
+    if !thresholds_valid(&[thresh, tj, tg, tf], total) { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+    vec![thresh, tj, tg, tf]
+}
+
+/// Optional `groups` argument: each group's members (from `guards`, each in one group only) and
+/// its internal threshold, plus `group_threshold`, the number of groups that must be satisfied
+fn group_args(guards: &[Key]) -> (Vec<GuardianGroup>, u8) {
+    let Some(groups) = runtime::try_get_named_arg::<Vec<GuardianGroup>>("groups") else { return (vec![], 0) };
+    let outer: u8 = runtime::get_named_arg("group_threshold");
+    if !groups_valid(&groups, outer, guards) { runtime::revert(ApiError::User(Err::BadGroups as u16)); }
+    (groups, outer)
+}
+
+/// Optional key configuration args: the weight of `owner_key` and the account's deployment and
+/// key management thresholds, all given or none
+fn key_config_args(owner_key: AccountHash) -> Option<KeyConfig> {
+    let weight: u8 = runtime::try_get_named_arg("owner_key_weight")?;
+    let dep: u8 = runtime::get_named_arg("deployment_threshold");
+    let km: u8 = runtime::get_named_arg("key_management_threshold");
+    if dep == 0 || km < dep { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+    Some((owner_key, (weight, dep, km)))
+}
+
+/// Store thresholds produced by `threshold_args`
+fn write_thresholds(acc: AccountHash, thresholds: &[u8]) {
+    for (prefix, t) in ["t", "tj", "tg", "tf"].iter().zip(thresholds) {
+        write(&format!("{}{:?}", prefix, acc), *t);
+    }
+}
+
+/// Threshold for one kind of action; accounts set up before per-action thresholds use their
+/// recovery approval threshold for everything
+fn action_threshold(prefix: &str, acc: AccountHash) -> u8 {
+    read(&format!("{}{:?}", prefix, acc)).unwrap_or_else(|| read(&format!("t{:?}", acc)).unwrap_or(2))
+}
+
+/// Record that a guardian just proved control of their key
+fn record_heartbeat(guard: &Key) {
+    write(&format!("hb{}", gid(guard)), now());
+}
+
+/// Guardians of `acc` who accepted their nomination. Accounts set up before nominations
+/// existed have no accepted list and all their guardians count.
+fn guardians(acc: AccountHash) -> Vec<Key> {
+    let guards = read_guardians(&format!("g{:?}", acc)).unwrap_or_default();
+    match read_guardians(&format!("gk{:?}", acc)) {
+        Some(accepted) => guards.into_iter().filter(|g| accepted.contains(g)).collect(),
+        None => guards,
+    }
+}
+
+/// Remove `acc` from the account list stored under `key`
+fn remove_account(key: &str, acc: AccountHash) {
+    let mut accounts: Vec<AccountHash> = read(key).unwrap_or(vec![]);
+    accounts.retain(|&a| a != acc);
+    write(key, accounts);
+}
+
+/// Replace `old` with `new` in the guardian list stored under `key`, if present
+fn replace_guardian(key: &str, old: Key, new: Key) {
+    let Some(mut guards) = read_guardians(key) else { return };
+    for g in guards.iter_mut().filter(|g| **g == old) { *g = new; }
+    write(key, guards);
+}
+
+/// Append the items stored under `from` to those under `to` and clear `from`
+fn move_list<T: CLTyped + FromBytes + ToBytes + PartialEq>(from: &str, to: &str) {
+    let mut moved: Vec<T> = read(to).unwrap_or_default();
+    for item in read::<Vec<T>>(from).unwrap_or_default() {
+        if !moved.contains(&item) { moved.push(item); }
+    }
+    write(to, moved);
+    write(from, Vec::<T>::new());
+}
+
+/// Reverts unless the caller may start a recovery for `acc` under its initiator policy.
+/// Returns true if the caller has to post a bond.
+fn check_initiator(acc: AccountHash) -> bool {
+    let caller = caller_key();
+    let caller_account = caller.into_account();
+    if caller_account.is_some() && backup_key(acc) == caller_account { return false; }
+    match read::<u8>(&format!("ip{:?}", acc)).unwrap_or(POLICY_ANYONE) {
+        POLICY_INITIATOR => {
+            if caller_account.is_none() || read::<AccountHash>(&format!("ii{:?}", acc)) != caller_account { runtime::revert(ApiError::User(Err::NotInitiator as u16)); }
+            false
+        }
+        POLICY_BOND => true,
+        POLICY_GUARDIANS => {
+            if !guardians(acc).contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+            false
+        }
+        _ => false,
+    }
+}
+
+/// A registry-owned purse stored under `name`, created on first use
+fn registry_purse(name: &str) -> URef {
+    match runtime::get_key(name) {
+        Some(k) => k.into_uref().unwrap_or_revert_with(ApiError::User(Err::MissingDict as u16)),
+        None => {
+            let purse = system::create_purse();
+            runtime::put_key(name, purse.into());
+            purse
+        }
+    }
+}
+
+/// Move the account's bond amount from the caller-supplied `purse` into the bond purse
+fn take_bond(acc: AccountHash, id: U256) {
+    let amount: U512 = read(&format!("ib{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::BadBond as u16));
+    let purse: URef = runtime::try_get_named_arg("purse").unwrap_or_revert_with(ApiError::User(Err::BondRequired as u16));
+    system::transfer_from_purse_to_purse(purse, registry_purse(BOND_PURSE), amount, None).unwrap_or_revert_with(ApiError::User(Err::BondRequired as u16));
+    write(&format!("rb{}", id), (runtime::get_caller(), amount));
+}
+
+/// Pay out a recovery's bond once: back to the initiator on refund, otherwise to the treasury or owner.
+/// A payment that fails leaves the bond unsettled in the bond purse rather than blocking the close.
+fn settle_bond(acc: AccountHash, id: U256, refund: bool) {
+    let Some((initiator, amount)) = read::<(AccountHash, U512)>(&format!("rb{}", id)) else { return };
+    let sk = format!("rs{}", id);
+    if read::<bool>(&sk).unwrap_or(false) { return; }
+
+    let from = registry_purse(BOND_PURSE);
+    let paid = match runtime::get_key(TREASURY).and_then(|k| k.into_uref()) {
+        Some(treasury) if !refund => system::transfer_from_purse_to_purse(from, treasury, amount, None).is_ok(),
+        _ => system::transfer_from_purse_to_account(from, if refund { initiator } else { acc }, amount, None).is_ok(),
+    };
+    if paid { write(&sk, true); }
+}
+
+/// Pay each guardian who approved a finalized recovery their reward from the account's escrow.
+/// A payment that fails leaves the reward in escrow rather than blocking finalization.
+fn pay_rewards(acc: AccountHash, id: U256) {
+    let reward: U512 = read(&format!("er{:?}", acc)).unwrap_or_default();
+    if reward.is_zero() { return; }
+    let bk = format!("eb{:?}", acc);
+    let mut balance: U512 = read(&bk).unwrap_or_default();
+
+    // Contract guardians have no main purse to pay into; their share stays in escrow
+    let guards = read_guardians(&format!("g{:?}", acc)).unwrap_or_default();
+    for guard in guards {
+        if balance < reward { break; }
+        let Some(to) = guard.into_account() else { continue };
+        if !read::<bool>(&format!("rp{}_{}", id, gid(&guard))).unwrap_or(false) { continue; }
+        if system::transfer_from_purse_to_account(registry_purse(ESCROW_PURSE), to, reward, None).is_ok() {
+            balance -= reward;
+        }
+    }
+    write(&bk, balance);
+}
+
+/// Close an open proposal without finalizing it
+fn close(acc: AccountHash, id: U256) {
+    write(&format!("rx{}", id), true);
+    write(&format!("ro{}", id), false);
+    remove_from_guardians(acc, id);
+
+    let ok = format!("ap{:?}", acc);
+    let mut open: Vec<U256> = read(&ok).unwrap_or(vec![]);
+    open.retain(|&o| o != id);
+    write(&ok, open);
+}
+
+/// A backup-key approval keeps the proposal alive for the expiry period after its delay ends
+fn is_expired(id: U256) -> bool {
+    read::<u64>(&format!("rw{}", id))
+        .or_else(|| read(&format!("re{}", id)))
+        .is_some_and(|since| now() >= since + RECOVERY_EXPIRY)
+}
+
+/// Approved by a guardian quorum, or by the backup key once its delay has passed. Drills never
+/// are, and a proposal closed without being finalized no longer is.
+fn approved(id: U256) -> bool {
+    ProposalApproval {
+        closed: is_closed(id),
+        drill: is_drill(id),
+        quorum: read::<bool>(&format!("ro{}", id)).unwrap_or(false),
+        backup_ready_at: read(&format!("rw{}", id)),
+    }
+    .approved(now())
+}
+
+/// Whether `id` is a drill started with `start_drill`
+fn is_drill(id: U256) -> bool {
+    read::<bool>(&format!("rd{}", id)).unwrap_or(false)
+}
+
+/// Whether recovery `id` was started under the account's inheritance mode
+fn is_inheritance(id: U256) -> bool {
+    read::<bool>(&format!("rh{}", id)).unwrap_or(false)
+}
+
+/// Inheritance is active once the owner has not pinged for the configured period
+fn inheritance_active(acc: AccountHash) -> bool {
+    let Some(period) = read::<u64>(&format!("hp{:?}", acc)) else { return false };
+    let last: u64 = read(&format!("hl{:?}", acc)).unwrap_or(0);
+    now() >= last + period
+}
+
+/// Accounts voting on recovery `id`: the beneficiary set for inheritance recoveries, accepted guardians otherwise
+fn voters(id: U256, acc: AccountHash) -> Vec<Key> {
+    if is_inheritance(id) {
+        read::<Vec<AccountHash>>(&format!("hs{:?}", acc)).unwrap_or_default().into_iter().map(Key::Account).collect()
+    } else {
+        guardians(acc)
+    }
+}
+
+/// Approvals needed to approve recovery `id`
+fn vote_threshold(id: U256, acc: AccountHash) -> u8 {
+    let key = if is_inheritance(id) { format!("ht{:?}", acc) } else { format!("t{:?}", acc) };
+    read(&key).unwrap_or(2)
+}
+
+/// Whether recovery `id` has enough approvals. With guardian groups, enough groups must each
+/// have enough approving members; otherwise `cnt` approvals must reach the threshold.
+fn quorum_met(id: U256, acc: AccountHash, cnt: u8) -> bool {
+    let groups: Vec<GuardianGroup> = read(&format!("gg{:?}", acc)).unwrap_or_default();
+    if is_inheritance(id) || groups.is_empty() { return cnt >= vote_threshold(id, acc); }
+
+    let outer: u8 = read(&format!("go{:?}", acc)).unwrap_or(1);
+    groups_satisfied(&groups, outer, |m| read::<bool>(&format!("rp{}_{}", id, gid(m))).unwrap_or(false))
+}
+
+/// Rejections needed to close recovery `id`
+fn reject_threshold(id: U256, acc: AccountHash) -> u8 {
+    if is_inheritance(id) { vote_threshold(id, acc) } else { action_threshold("tj", acc) }
+}
+
+/// The account's self-owned backup key, if one is set
+fn backup_key(acc: AccountHash) -> Option<AccountHash> {
+    read::<Option<AccountHash>>(&format!("bk{:?}", acc)).flatten()
+}
+
+/// Whether a proposal was closed without being finalized: cancelled, rejected, expired or
+/// auto-closed by a competing proposal's finalization
+fn is_closed(id: U256) -> bool {
+    read::<bool>(&format!("rx{}", id)).unwrap_or(false)
+}
+
+/// A proposal is open until it is finalized or closed
+fn is_open(id: U256) -> bool {
+    !read::<bool>(&format!("rf{}", id)).unwrap_or(false) && !is_closed(id)
+}
+
+/// Remove a recovery ID from each guardian's active recoveries list
+fn remove_from_guardians(acc: AccountHash, id: U256) {
+    let guards = if is_inheritance(id) { voters(id, acc) } else { read_guardians(&format!("g{:?}", acc)).unwrap_or_default() };
+    for guard in &guards {
+        let key = format!("gr{}", gid(guard));
+        let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
+        recoveries.retain(|&r| r != id);
+        write(&key, recoveries);
+    }
+}
+
+/// Whether guardians froze the account, presuming its owner key stolen
+fn frozen(acc: AccountHash) -> bool {
+    read::<bool>(&format!("fz{:?}", acc)).unwrap_or(false)
+}
+
+/// Reverts unless the caller is the account's owner and the account is not frozen: while frozen
+/// the owner key is presumed stolen and may not change any of the account's settings
+fn unfrozen_owner(acc: AccountHash) {
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::AlreadyFrozen as u16)); }
+}
+
+/// Freeze votes cast within the last `FREEZE_VOTE_TTL`: (guardian, cast at)
+fn live_freeze_votes(acc: AccountHash) -> Vec<(Key, u64)> {
+    let mut votes: Vec<(Key, u64)> = read(&format!("fv{:?}", acc)).unwrap_or_default();
+    votes.retain(|(_, at)| at + FREEZE_VOTE_TTL > now());
+    votes
+}
+
+/// Reverts unless the caller is the account the recovery was started for
+fn recovery_account_caller(id: U256) -> AccountHash {
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotRecoveryAccount as u16)); }
+    acc
+}
+
+/// Reverts unless a soft retirement for this recovery is still pending
+fn pending_retirement_end(id: U256) -> u64 {
+    if read::<u8>(&format!("xs{}", id)) != Some(RETIREMENT_PENDING) { runtime::revert(ApiError::User(Err::NoRetirement as u16)); }
+    read(&format!("xe{}", id)).unwrap_or_revert_with(ApiError::User(Err::NoRetirement as u16))
+}
+
 #[no_mangle]
 pub extern "C" fn init_guardians() {
     let acc: AccountHash = runtime::get_named_arg("account");
-    let guards: Vec<AccountHash> = runtime::get_named_arg("guardians");
-    let thresh: u8 = runtime::get_named_arg("threshold");
+    let guards = guardian_list_arg();
+    // Account hashes of the keys of printable recovery codes, each acting as one guardian
+    let codes: Vec<[u8; 32]> = runtime::try_get_named_arg("recovery_codes").unwrap_or_default();
 
+    let total = guards.len() + codes.len();
     if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
-    if guards.len() < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
-    if thresh == 0 || thresh as usize > guards.len() { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+    if total < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if codes.iter().enumerate().any(|(i, c)| codes[..i].contains(c)) { runtime::revert(ApiError::User(Err::BadCode as u16)); }
+    let thresholds = threshold_args(total);
+    let (groups, outer) = group_args(&guards);
 
     let k = format!("i{:?}", acc);
     if read::<bool>(&k).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyInit as u16)); }
 
     write(&format!("g{:?}", acc), guards.clone());
-    write(&format!("t{:?}", acc), thresh);
+    write(&format!("gk{:?}", acc), Vec::<Key>::new());
+    write(&format!("gh{:?}", acc), codes);
+    write_thresholds(acc, &thresholds);
+    write(&format!("gg{:?}", acc), groups);
+    write(&format!("go{:?}", acc), outer);
+    if let Some(config) = key_config_args(acc) { write(&format!("kc{:?}", acc), config); }
     write(&k, true);
 
-    // Add reverse mapping: for each guardian, add this account to their protected list
+    // Guardians start out pending: record the nomination so they can find and accept it
     for guard in &guards {
-        let key = format!("ga{:?}", guard);
+        let key = format!("gn{}", gid(guard));
+        let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+        if !protected.contains(&acc) {
+            protected.push(acc);
+            write(&key, protected);
+        }
+    }
+}
+
+/// Nominated guardian accepts; only accepted guardians may start, approve or reject recoveries
+#[no_mangle]
+pub extern "C" fn accept_guardianship() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let caller = caller_key();
+
+    let guards = read_guardians(&format!("g{:?}", acc)).unwrap_or_default();
+    if !guards.contains(&caller) { runtime::revert(ApiError::User(Err::NotNominated as u16)); }
+    let kk = format!("gk{:?}", acc);
+    let mut accepted = read_guardians(&kk).unwrap_or_revert_with(ApiError::User(Err::AlreadyAccepted as u16));
+    if accepted.contains(&caller) { runtime::revert(ApiError::User(Err::AlreadyAccepted as u16)); }
+    accepted.push(caller);
+    write(&kk, accepted);
+    record_heartbeat(&caller);
+
+    remove_account(&format!("gn{}", gid(&caller)), acc);
+    let key = format!("ga{}", gid(&caller));
+    let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
+    if !protected.contains(&acc) {
+        protected.push(acc);
+        write(&key, protected);
+    }
+}
+
+/// Nominated guardian declines; they are dropped from the account's guardian list and groups.
+/// Reverts if the remaining guardians and codes could no longer reach a threshold or group.
+#[no_mangle]
+pub extern "C" fn decline_guardianship() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let caller = caller_key();
+
+    let gk = format!("g{:?}", acc);
+    let mut guards = read_guardians(&gk).unwrap_or_default();
+    if !guards.contains(&caller) { runtime::revert(ApiError::User(Err::NotNominated as u16)); }
+    if guardians(acc).contains(&caller) { runtime::revert(ApiError::User(Err::AlreadyAccepted as u16)); }
+    guards.retain(|&g| g != caller);
+
+    let codes: Vec<[u8; 32]> = read(&format!("gh{:?}", acc)).unwrap_or_default();
+    let thresholds = ["t", "tj", "tg", "tf"].map(|prefix| action_threshold(prefix, acc));
+    if !thresholds_valid(&thresholds, guards.len() + codes.len()) { runtime::revert(ApiError::User(Err::QuorumUnreachable as u16)); }
+    let ggk = format!("gg{:?}", acc);
+    let mut groups: Vec<GuardianGroup> = read(&ggk).unwrap_or_default();
+    if !groups.is_empty() {
+        for (members, _) in groups.iter_mut() { members.retain(|m| *m != caller); }
+        if !groups_valid(&groups, read(&format!("go{:?}", acc)).unwrap_or(1), &guards) { runtime::revert(ApiError::User(Err::QuorumUnreachable as u16)); }
+        write(&ggk, groups);
+    }
+
+    write(&gk, guards);
+    remove_account(&format!("gn{}", gid(&caller)), acc);
+}
+
+/// Owner proposes a new guardian set and thresholds. It takes effect once
+/// `guardian_change_threshold` accepted guardians approve it, or at once if that is 0.
+#[no_mangle]
+pub extern "C" fn propose_guardians() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guards = guardian_list_arg();
+    unfrozen_owner(acc);
+    if !read::<bool>(&format!("i{:?}", acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+
+    let codes: Vec<[u8; 32]> = read(&format!("gh{:?}", acc)).unwrap_or_default();
+    let total = guards.len() + codes.len();
+    if total < 2 { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    let thresholds = threshold_args(total);
+    let (groups, outer) = group_args(&guards);
+
+    write(&format!("pn{:?}", acc), guards);
+    write(&format!("gq{:?}", acc), thresholds);
+    write(&format!("pg{:?}", acc), groups);
+    write(&format!("po{:?}", acc), outer);
+    write(&format!("gv{:?}", acc), Vec::<Key>::new());
+}
+
+/// Accepted guardian approves the pending guardian-set change
+#[no_mangle]
+pub extern "C" fn approve_guardians() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let caller = caller_key();
+    if !guardians(acc).contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    let thresholds: Vec<u8> = read(&format!("gq{:?}", acc)).unwrap_or_default();
+    if thresholds.is_empty() { runtime::revert(ApiError::User(Err::NoProposal as u16)); }
+
+    let vk = format!("gv{:?}", acc);
+    let mut votes: Vec<Key> = read(&vk).unwrap_or_default();
+    if votes.contains(&caller) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+    votes.push(caller);
+    record_heartbeat(&caller);
+
+    let enough = votes.len() >= action_threshold("tg", acc) as usize;
+    write(&vk, votes);
+    if enough { apply_guardians(acc); }
+}
+
+/// Get the pending guardian-set change: (guardians, thresholds, approvals so far)
+#[no_mangle]
+pub extern "C" fn get_guardian_proposal() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let guards: Vec<Key> = read(&format!("pn{:?}", acc)).unwrap_or_default();
+    let thresholds: Vec<u8> = read(&format!("gq{:?}", acc)).unwrap_or_default();
+    let votes: Vec<Key> = read(&format!("gv{:?}", acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((guards, thresholds, votes.len() as u8)).unwrap_or_revert());
+}
+
+/// Get an account's thresholds: recovery approval, rejection, guardian-set change, freeze and inheritance
+#[no_mangle]
+pub extern "C" fn get_thresholds() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let thresholds: Vec<u8> = vec![
+        read(&format!("t{:?}", acc)).unwrap_or(2),
+        action_threshold("tj", acc),
+        action_threshold("tg", acc),
+        action_threshold("tf", acc),
+        read(&format!("ht{:?}", acc)).unwrap_or(0),
+    ];
+    runtime::ret(CLValue::from_t(thresholds).unwrap_or_revert());
+}
+
+/// Get an account's guardian groups and how many of them must be satisfied; empty when the
+/// account uses a flat threshold
+#[no_mangle]
+pub extern "C" fn get_guardian_groups() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let groups: Vec<GuardianGroup> = read(&format!("gg{:?}", acc)).unwrap_or_default();
+    let outer: u8 = read(&format!("go{:?}", acc)).unwrap_or(0);
+    runtime::ret(CLValue::from_t((groups, outer)).unwrap_or_revert());
+}
+
+/// Replace the guardian set and thresholds with the pending proposal. Retained guardians stay
+/// accepted; new ones are nominated and must accept.
+fn apply_guardians(acc: AccountHash) {
+    if !read::<Vec<U256>>(&format!("ap{:?}", acc)).unwrap_or_default().is_empty() { runtime::revert(ApiError::User(Err::RecoveryInProgress as u16)); }
+    // A missing or unreadable proposal must not wipe out the guardians
+    let new: Vec<Key> = read(&format!("pn{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NoProposal as u16));
+    let thresholds: Vec<u8> = read(&format!("gq{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NoProposal as u16));
+    if thresholds.is_empty() { runtime::revert(ApiError::User(Err::NoProposal as u16)); }
+    let old = read_guardians(&format!("g{:?}", acc)).unwrap_or_default();
+    let mut accepted = guardians(acc);
+    accepted.retain(|g| new.contains(g));
+
+    for guard in old.iter().filter(|g| !new.contains(g)) {
+        remove_account(&format!("ga{}", gid(guard)), acc);
+        remove_account(&format!("gn{}", gid(guard)), acc);
+    }
+    for guard in new.iter().filter(|g| !old.contains(g)) {
+        let key = format!("gn{}", gid(guard));
         let mut protected: Vec<AccountHash> = read(&key).unwrap_or(vec![]);
         if !protected.contains(&acc) {
             protected.push(acc);
             write(&key, protected);
         }
     }
+
+    write(&format!("g{:?}", acc), new);
+    write(&format!("gk{:?}", acc), accepted);
+    write_thresholds(acc, &thresholds);
+    let groups: Vec<GuardianGroup> = read(&format!("pg{:?}", acc)).unwrap_or_default();
+    write(&format!("gg{:?}", acc), groups);
+    write(&format!("go{:?}", acc), read::<u8>(&format!("po{:?}", acc)).unwrap_or(0));
+    write(&format!("pg{:?}", acc), Vec::<(Vec<Key>, u8)>::new());
+    write(&format!("pn{:?}", acc), Vec::<Key>::new());
+    write(&format!("gq{:?}", acc), Vec::<u8>::new());
+    write(&format!("gv{:?}", acc), Vec::<Key>::new());
 }
 
 #[no_mangle]
 pub extern "C" fn start_recovery() {
     let acc: AccountHash = runtime::get_named_arg("account");
-    let nk: PublicKey = runtime::get_named_arg("new_key");
+    // Either a list of (key, weight) targets or a single new_key at the default weight
+    let targets: Vec<(PublicKey, u8)> = match runtime::try_get_named_arg("new_keys") {
+        Some(t) => t,
+        None => vec![(runtime::get_named_arg::<PublicKey>("new_key"), DEFAULT_KEY_WEIGHT)],
+    };
+    if targets.is_empty() || targets.iter().any(|(_, w)| *w == 0) { runtime::revert(ApiError::User(Err::BadTargets as u16)); }
 
     if !read::<bool>(&format!("i{:?}", acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
-    if read::<U256>(&format!("a{:?}", acc)).is_some() { runtime::revert(ApiError::User(Err::RecoveryExists as u16)); }
+    let bonded = check_initiator(acc);
+
+    let id = open_recovery(acc, targets, bonded, false);
+    runtime::ret(CLValue::from_t(id).unwrap_or_revert());
+}
 
+/// Record a new open proposal for `acc` and return its id
+fn open_recovery(acc: AccountHash, targets: Vec<(PublicKey, u8)>, bonded: bool, inheritance: bool) -> U256 {
     let id = read::<U256>("c").unwrap_or(U256::zero()) + 1;
     write("c", id);
+    if bonded { take_bond(acc, id); }
+    if inheritance { write(&format!("rh{}", id), true); }
+    write(&format!("re{}", id), now());
     write(&format!("ra{}", id), acc);
-    write(&format!("rk{}", id), nk);
+
+    // Each proposal takes the account's next approval nonce, binding off-chain signatures to it
+    let nk = format!("an{:?}", acc);
+    let nonce: u64 = read(&nk).unwrap_or(0);
+    write(&nk, nonce + 1);
+    write(&format!("rn{}", id), nonce);
+    write(&format!("rt{}", id), targets);
     write(&format!("rc{}", id), 0u8);
     write(&format!("ro{}", id), false);
-    write(&format!("a{:?}", acc), id);
+
+    // Several proposals may compete for the same account until one is finalized
+    let ok = format!("ap{:?}", acc);
+    let mut open: Vec<U256> = read(&ok).unwrap_or(vec![]);
+    open.push(id);
+    write(&ok, open);
 
     // Add reverse mapping: for each guardian, add this recovery ID to their active recoveries list
-    let guards: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or(vec![]);
-    for guard in &guards {
-        let key = format!("gr{:?}", guard);
+    for guard in &voters(id, acc) {
+        let key = format!("gr{}", gid(guard));
         let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
         if !recoveries.contains(&id) {
             recoveries.push(id);
@@ -113,65 +770,843 @@ pub extern "C" fn start_recovery() {
         }
     }
 
+    id
+}
+
+/// Owner starts a drill: a recovery-like record guardians approve through `approve` to prove they
+/// can respond. It has no targets, is never approved and cannot be finalized. Returns its id.
+#[no_mangle]
+pub extern "C" fn start_drill() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    unfrozen_owner(acc);
+    if !read::<bool>(&format!("i{:?}", acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+
+    let id = read::<U256>("c").unwrap_or(U256::zero()) + 1;
+    write("c", id);
+    write(&format!("rd{}", id), true);
+    write(&format!("re{}", id), now());
+    write(&format!("ra{}", id), acc);
+    write(&format!("rc{}", id), 0u8);
+    write(&format!("ro{}", id), false);
+
+    let dk = format!("dr{:?}", acc);
+    let mut drills: Vec<U256> = read(&dk).unwrap_or_default();
+    drills.push(id);
+    write(&dk, drills);
+
+    // Guardians find drills alongside real recoveries
+    for guard in &guardians(acc) {
+        let key = format!("gr{}", gid(guard));
+        let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
+        recoveries.push(id);
+        write(&key, recoveries);
+    }
+
+    runtime::ret(CLValue::from_t(id).unwrap_or_revert());
+}
+
+/// Owner ends a drill, removing it from the guardians' active recoveries
+#[no_mangle]
+pub extern "C" fn end_drill() {
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = recovery_account_caller(id);
+    if frozen(acc) { runtime::revert(ApiError::User(Err::AlreadyFrozen as u16)); }
+    if !is_drill(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    close(acc, id);
+}
+
+/// Get an account's drill ids
+#[no_mangle]
+pub extern "C" fn get_drills() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let drills: Vec<U256> = read(&format!("dr{:?}", acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t(drills).unwrap_or_revert());
+}
+
+/// Readiness report of a drill: each accepted guardian's response time in ms (None if they
+/// have not approved) and whether the responses reached the threshold
+#[no_mangle]
+pub extern "C" fn get_drill_report() {
+    let id: U256 = runtime::get_named_arg("id");
+    if !is_drill(id) { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+
+    let responses: Vec<(Key, Option<u64>)> = guardians(acc).into_iter()
+        .map(|g| { let t = read::<u64>(&format!("rr{}_{}", id, gid(&g))); (g, t) })
+        .collect();
+    let cnt: u8 = read(&format!("rc{}", id)).unwrap_or(0);
+    runtime::ret(CLValue::from_t((responses, quorum_met(id, acc, cnt))).unwrap_or_revert());
+}
+
+/// Owner sets up inheritance: if they stop pinging for `period` ms, `threshold` of the
+/// `beneficiaries` may start and approve a recovery rotating the account to `beneficiary`
+#[no_mangle]
+pub extern "C" fn set_inheritance() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let beneficiary: PublicKey = runtime::get_named_arg("beneficiary");
+    let beneficiaries: Vec<AccountHash> = runtime::get_named_arg("beneficiaries");
+    let thresh: u8 = runtime::get_named_arg("threshold");
+    let period: u64 = runtime::get_named_arg("period");
+
+    unfrozen_owner(acc);
+    if beneficiaries.is_empty() { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if thresh == 0 || thresh as usize > beneficiaries.len() { runtime::revert(ApiError::User(Err::BadThreshold as u16)); }
+    if period == 0 { runtime::revert(ApiError::User(Err::BadDelay as u16)); }
+
+    write(&format!("hk{:?}", acc), beneficiary);
+    write(&format!("hs{:?}", acc), beneficiaries);
+    write(&format!("ht{:?}", acc), thresh);
+    write(&format!("hp{:?}", acc), period);
+    write(&format!("hl{:?}", acc), now());
+}
+
+/// Owner proves they are still in control, resetting the inheritance timer
+#[no_mangle]
+pub extern "C" fn ping() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    unfrozen_owner(acc);
+    write(&format!("hl{:?}", acc), now());
+}
+
+/// Beneficiary starts an inheritance recovery once the owner has gone silent; returns its id
+#[no_mangle]
+pub extern "C" fn start_inheritance() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let beneficiary: PublicKey = read(&format!("hk{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NoInheritance as u16));
+    let beneficiaries: Vec<AccountHash> = read(&format!("hs{:?}", acc)).unwrap_or_default();
+
+    if !beneficiaries.contains(&runtime::get_caller()) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if !inheritance_active(acc) { runtime::revert(ApiError::User(Err::OwnerActive as u16)); }
+
+    let id = open_recovery(acc, vec![(beneficiary, DEFAULT_KEY_WEIGHT)], false, true);
     runtime::ret(CLValue::from_t(id).unwrap_or_revert());
 }
 
+/// Called by the account's own sweep session: returns the beneficiary account of an approved,
+/// still open inheritance recovery so the account's funds can be moved to it
+#[no_mangle]
+pub extern "C" fn get_beneficiary() {
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = recovery_account_caller(id);
+    if !is_inheritance(id) { runtime::revert(ApiError::User(Err::NoInheritance as u16)); }
+    if !approved(id) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    if !inheritance_active(acc) { runtime::revert(ApiError::User(Err::OwnerActive as u16)); }
+
+    let beneficiary: PublicKey = read(&format!("hk{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NoInheritance as u16));
+    runtime::ret(CLValue::from_t(AccountHash::from(&beneficiary)).unwrap_or_revert());
+}
+
+/// Guardian votes to freeze an account whose key was reported stolen. Once the votes reach the
+/// freeze threshold the account is frozen and `freeze_account.wasm` may raise its deployment threshold.
+#[no_mangle]
+pub extern "C" fn vote_freeze() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let caller = caller_key();
+
+    if !guardians(acc).contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::AlreadyFrozen as u16)); }
+
+    // Lapsed votes are dropped; the freeze needs a quorum voting within FREEZE_VOTE_TTL
+    let mut votes = live_freeze_votes(acc);
+    if votes.iter().any(|(v, _)| *v == caller) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+    votes.push((caller, now()));
+    record_heartbeat(&caller);
+
+    if votes.len() >= action_threshold("tf", acc) as usize {
+        write(&format!("fz{:?}", acc), true);
+        write(&format!("ft{:?}", acc), now());
+        votes.clear();
+    }
+    write(&format!("fv{:?}", acc), votes);
+}
+
+/// Called by the account's unfreeze session once a recovery has been finalized since the freeze
+#[no_mangle]
+pub extern "C" fn unfreeze() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    if runtime::get_caller() != acc { runtime::revert(ApiError::User(Err::NotOwner as u16)); }
+
+    let fk = format!("fz{:?}", acc);
+    if !read::<bool>(&fk).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotFrozen as u16)); }
+    let frozen_at: u64 = read(&format!("ft{:?}", acc)).unwrap_or(0);
+    if read::<u64>(&format!("lf{:?}", acc)).is_none_or(|finalized| finalized < frozen_at) {
+        runtime::revert(ApiError::User(Err::NotRecovered as u16));
+    }
+    write(&fk, false);
+}
+
+/// Get an account's freeze state: (frozen, frozen since, pending freeze votes that have not lapsed)
+#[no_mangle]
+pub extern "C" fn get_freeze() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let frozen = frozen(acc);
+    let since: u64 = if frozen { read(&format!("ft{:?}", acc)).unwrap_or(0) } else { 0 };
+    let votes = live_freeze_votes(acc).len() as u8;
+    runtime::ret(CLValue::from_t((frozen, since, votes)).unwrap_or_revert());
+}
+
+/// Get an account's inheritance config: (beneficiary key, beneficiaries, threshold)
+#[no_mangle]
+pub extern "C" fn get_inheritance() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let beneficiary: Option<PublicKey> = read(&format!("hk{:?}", acc));
+    let beneficiaries: Vec<AccountHash> = read(&format!("hs{:?}", acc)).unwrap_or_default();
+    let thresh: u8 = read(&format!("ht{:?}", acc)).unwrap_or(0);
+    runtime::ret(CLValue::from_t((beneficiary, beneficiaries, thresh)).unwrap_or_revert());
+}
+
+/// Get an account's inheritance timer: (period, last ping, whether inheritance is active)
+#[no_mangle]
+pub extern "C" fn get_inheritance_status() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let period: u64 = read(&format!("hp{:?}", acc)).unwrap_or(0);
+    let last: u64 = read(&format!("hl{:?}", acc)).unwrap_or(0);
+    runtime::ret(CLValue::from_t((period, last, inheritance_active(acc))).unwrap_or_revert());
+}
+
 #[no_mangle]
 pub extern "C" fn approve() {
     let id: U256 = runtime::get_named_arg("id");
-    let caller = runtime::get_caller();
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let caller = caller_key();
+    if caller.into_account().is_some_and(|c| backup_key(acc) == Some(c)) {
+        backup_approval(id, acc);
+    } else {
+        record_approval(id, acc, &caller);
+    }
+}
+
+/// The backup key approves alone, but only after the account's delay; the owner can cancel meanwhile
+fn backup_approval(id: U256, acc: AccountHash) {
+    // Inheritance recoveries are voted by the beneficiaries, and drills test the guardians
+    if is_inheritance(id) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if is_drill(id) { runtime::revert(ApiError::User(Err::IsDrill as u16)); }
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    if is_expired(id) { runtime::revert(ApiError::User(Err::Expired as u16)); }
+    let wk = format!("rw{}", id);
+    if read::<u64>(&wk).is_some() { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+
+    let delay: u64 = read(&format!("bd{:?}", acc)).unwrap_or(DEFAULT_BACKUP_DELAY);
+    write(&wk, now() + delay);
+}
+
+/// Approvals signed off-chain by guardians and submitted together by a relayer. Each entry is a
+/// guardian's public key and its signature over the recovery's approval message; entries that
+/// don't count (bad signature, not a guardian, already approved, ...) are skipped, and the call
+/// reverts only if none of them does.
+#[no_mangle]
+pub extern "C" fn approve_with_signatures() {
+    let id: U256 = runtime::get_named_arg("id");
+    let approvals: Vec<(PublicKey, Bytes)> = runtime::get_named_arg("approvals");
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    let msg = approval_message(id, acc);
+
+    let mut last = Err::BadSignature;
+    let mut counted = 0;
+    for (public_key, signature) in approvals {
+        let verified = match Signature::from_bytes(&signature) {
+            Ok((signature, [])) => cryptography::verify_signature(&msg, &signature, &public_key).is_ok(),
+            _ => false,
+        };
+        if !verified {
+            last = Err::BadSignature;
+            continue;
+        }
+        match try_approval(id, acc, &Key::Account(AccountHash::from(&public_key))) {
+            Ok(()) => counted += 1,
+            Err(e) => last = e,
+        }
+    }
+    if counted == 0 { runtime::revert(ApiError::User(last as u16)); }
+}
+
+/// Network name off-chain signatures are bound to, reverting if the registry was installed without one
+fn chain_name() -> String {
+    runtime::get_key(CHAIN_NAME)
+        .and_then(|k| k.into_uref())
+        .and_then(|u| storage::read(u).unwrap_or(None))
+        .unwrap_or_revert_with(ApiError::User(Err::NoChainName as u16))
+}
+
+/// Bytes a guardian's wallet signs to approve recovery `id` of `acc` off-chain
+fn approval_message(id: U256, acc: AccountHash) -> Vec<u8> {
+    let msg = ApprovalMessage {
+        chain_name: chain_name(),
+        registry: registry_hash(),
+        recovery_id: id,
+        account: acc,
+        targets: read(&format!("rt{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16)),
+        nonce: read(&format!("rn{}", id)).unwrap_or(0),
+    };
+    msg.signed_bytes().unwrap_or_revert()
+}
+
+/// Count `caller`'s approval of recovery `id`, marking it approved once the threshold is met
+fn record_approval(id: U256, acc: AccountHash, caller: &Key) {
+    if let Err(e) = try_approval(id, acc, caller) { runtime::revert(ApiError::User(e as u16)); }
+}
+
+/// Count `caller`'s approval of recovery `id` if it is allowed, or return why it is not
+fn try_approval(id: U256, acc: AccountHash, caller: &Key) -> Result<(), Err> {
+    if !voters(id, acc).contains(caller) { return Err(Err::NotGuardian); }
+    if is_inheritance(id) && !inheritance_active(acc) { return Err(Err::OwnerActive); }
+    if !is_open(id) { return Err(Err::Closed); }
+    if is_expired(id) { return Err(Err::Expired); }
+
+    let ak = format!("rp{}_{}", id, gid(caller));
+    if read::<bool>(&ak).unwrap_or(false) { return Err(Err::AlreadyApproved); }
+    if read::<bool>(&format!("rq{}_{}", id, gid(caller))).unwrap_or(false) { return Err(Err::AlreadyRejected); }
+
+    if !is_drill(id) && backs_other(id, acc, caller) { return Err(Err::ApprovedOther); }
+
+    write(&ak, true);
+    if is_drill(id) {
+        let started: u64 = read(&format!("re{}", id)).unwrap_or(0);
+        write(&format!("rr{}_{}", id, gid(caller)), now() - started);
+    }
+    record_heartbeat(caller);
+    count_approval(id, acc);
+    Ok(())
+}
+
+/// Whether `voter` already approved another open proposal of `acc`. A guardian or recovery code
+/// backs at most one competing proposal at a time; drills don't compete.
+fn backs_other(id: U256, acc: AccountHash, voter: &Key) -> bool {
+    let open: Vec<U256> = read(&format!("ap{:?}", acc)).unwrap_or(vec![]);
+    open.iter().any(|o| *o != id && read::<bool>(&format!("rp{}_{}", o, gid(voter))).unwrap_or(false))
+}
+
+/// Add one approval to recovery `id`, marking it approved once the threshold is met
+fn count_approval(id: U256, acc: AccountHash) {
+    let cnt: u8 = read(&format!("rc{}", id)).unwrap_or(0) + 1;
+    write(&format!("rc{}", id), cnt);
+
+    if quorum_met(id, acc, cnt) { write(&format!("ro{}", id), true); }
+}
+
+/// Approve with a recovery code of the account: its key signs the recovery's approval message,
+/// the signature counts as one guardian approval and the code is burned
+#[no_mangle]
+pub extern "C" fn approve_with_code() {
+    let id: U256 = runtime::get_named_arg("id");
+    let code_key: PublicKey = runtime::get_named_arg("code_key");
+    let signature: Bytes = runtime::get_named_arg("signature");
+
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    if is_expired(id) { runtime::revert(ApiError::User(Err::Expired as u16)); }
+    // Codes stand in for guardians; inheritance recoveries are voted on by beneficiaries only
+    if is_inheritance(id) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    // Drills measure guardian responsiveness and must not burn a code
+    if is_drill(id) { runtime::revert(ApiError::User(Err::IsDrill as u16)); }
+    // Codes belong to no group and could never help satisfy a group policy
+    if !read::<Vec<GuardianGroup>>(&format!("gg{:?}", acc)).unwrap_or_default().is_empty() { runtime::revert(ApiError::User(Err::BadGroups as u16)); }
+
+    let ck = format!("gh{:?}", acc);
+    let mut codes: Vec<[u8; 32]> = read(&ck).unwrap_or_default();
+    let hash = AccountHash::from(&code_key).value();
+    let Some(pos) = codes.iter().position(|c| *c == hash) else { runtime::revert(ApiError::User(Err::BadCode as u16)) };
+    cryptography::verify_signature(approval_message(id, acc), &parse_signature(&signature), &code_key)
+        .unwrap_or_revert_with(ApiError::User(Err::BadSignature as u16));
+    // The code votes like a guardian keyed by its account hash
+    let voter = Key::Account(AccountHash::new(hash));
+    if backs_other(id, acc, &voter) { runtime::revert(ApiError::User(Err::ApprovedOther as u16)); }
+    codes.remove(pos);
+    write(&ck, codes);
+    write(&format!("rp{}_{}", id, gid(&voter)), true);
+
+    count_approval(id, acc);
+}
+
+/// Decode a bytesrepr-encoded `Signature`, reverting if it is malformed
+fn parse_signature(bytes: &Bytes) -> Signature {
+    match Signature::from_bytes(bytes) {
+        Ok((signature, [])) => signature,
+        _ => runtime::revert(ApiError::User(Err::BadSignature as u16)),
+    }
+}
+
+/// Get the number of unused recovery codes of an account
+#[no_mangle]
+pub extern "C" fn get_recovery_code_count() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let codes: Vec<[u8; 32]> = read(&format!("gh{:?}", acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t(codes.len() as u32).unwrap_or_revert());
+}
+
+/// Owner sets (or clears with None) a backup key of their own that can start and approve
+/// recoveries alone, with its approvals taking effect `delay` ms later
+#[no_mangle]
+pub extern "C" fn set_backup_key() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let backup: Option<PublicKey> = runtime::get_named_arg("backup_key");
+    let delay: u64 = runtime::get_named_arg("delay");
+
+    unfrozen_owner(acc);
+    let backup = backup.map(|pk| AccountHash::from(&pk));
+    if backup == Some(acc) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+
+    write(&format!("bk{:?}", acc), backup);
+    // Removing the key ignores the delay
+    if backup.is_some() {
+        if delay < MIN_BACKUP_DELAY { runtime::revert(ApiError::User(Err::BadDelay as u16)); }
+        write(&format!("bd{:?}", acc), delay);
+    }
+}
+
+/// Get an account's backup key and its approval delay
+#[no_mangle]
+pub extern "C" fn get_backup_key() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let delay: u64 = read(&format!("bd{:?}", acc)).unwrap_or(DEFAULT_BACKUP_DELAY);
+    runtime::ret(CLValue::from_t((backup_key(acc), delay)).unwrap_or_revert());
+}
+
+/// Get when a backup-key approval of a recovery takes effect, 0 if the backup key has not approved
+#[no_mangle]
+pub extern "C" fn get_backup_approval() {
+    let id: U256 = runtime::get_named_arg("id");
+    runtime::ret(CLValue::from_t(read::<u64>(&format!("rw{}", id)).unwrap_or(0)).unwrap_or_revert());
+}
+
+/// Get the approval nonce a recovery's off-chain approval messages must carry
+#[no_mangle]
+pub extern "C" fn get_approval_nonce() {
+    let id: U256 = runtime::get_named_arg("id");
+    if read::<AccountHash>(&format!("ra{}", id)).is_none() { runtime::revert(ApiError::User(Err::NotFound as u16)); }
+    runtime::ret(CLValue::from_t(read::<u64>(&format!("rn{}", id)).unwrap_or(0)).unwrap_or_revert());
+}
+
+/// Withdraw an approval so the guardian can back a competing proposal
+#[no_mangle]
+pub extern "C" fn revoke() {
+    let id: U256 = runtime::get_named_arg("id");
+    let caller = caller_key();
+
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+
+    let ak = format!("rp{}_{}", id, gid(&caller));
+    if !read::<bool>(&ak).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+
+    write(&ak, false);
+    let cnt: u8 = read::<u8>(&format!("rc{}", id)).unwrap_or(1) - 1;
+    write(&format!("rc{}", id), cnt);
+
+    write(&format!("ro{}", id), quorum_met(id, acc, cnt));
+}
+
+#[no_mangle]
+pub extern "C" fn is_approved() {
+    let id: U256 = runtime::get_named_arg("id");
+    runtime::ret(CLValue::from_t(approved(id)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn finalize() {
+    let id: U256 = runtime::get_named_arg("id");
+    if is_drill(id) { runtime::revert(ApiError::User(Err::IsDrill as u16)); }
+    if !approved(id) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+
+    // Get the target account for this recovery
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if is_inheritance(id) && !inheritance_active(acc) { runtime::revert(ApiError::User(Err::OwnerActive as u16)); }
+
+    remove_from_guardians(acc, id);
+    write(&format!("rf{}", id), true); // Recovery finalized flag
+    write(&format!("lf{:?}", acc), now()); // Lets a frozen account be unfrozen
+    settle_bond(acc, id, true);
+    pay_rewards(acc, id);
+
+    // Auto-close every competing proposal for this account; losing the race is not misconduct,
+    // so their bonds are refunded
+    let ok = format!("ap{:?}", acc);
+    let open: Vec<U256> = read(&ok).unwrap_or(vec![]);
+    for other in open.into_iter().filter(|o| *o != id) {
+        close(acc, other);
+        settle_bond(acc, other, true);
+    }
+    write(&ok, Vec::<U256>::new());
+}
+
+/// Owner funds the account's guardian reward escrow from `purse` and sets the per-guardian reward
+#[no_mangle]
+pub extern "C" fn deposit_escrow() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let purse: URef = runtime::get_named_arg("purse");
+    let amount: U512 = runtime::get_named_arg("amount");
+    let reward: U512 = runtime::get_named_arg("reward");
+
+    unfrozen_owner(acc);
+    system::transfer_from_purse_to_purse(purse, registry_purse(ESCROW_PURSE), amount, None).unwrap_or_revert_with(ApiError::User(Err::BadEscrow as u16));
+
+    let bk = format!("eb{:?}", acc);
+    let balance: U512 = read(&bk).unwrap_or_default();
+    write(&bk, balance + amount);
+    write(&format!("er{:?}", acc), reward);
+}
+
+/// Owner withdraws the whole escrow while no recovery is in progress
+#[no_mangle]
+pub extern "C" fn withdraw_escrow() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    unfrozen_owner(acc);
+    if !read::<Vec<U256>>(&format!("ap{:?}", acc)).unwrap_or_default().is_empty() { runtime::revert(ApiError::User(Err::RecoveryInProgress as u16)); }
+
+    let bk = format!("eb{:?}", acc);
+    let balance: U512 = read(&bk).unwrap_or_default();
+    if balance.is_zero() { runtime::revert(ApiError::User(Err::BadEscrow as u16)); }
+    write(&bk, U512::zero());
+    system::transfer_from_purse_to_account(registry_purse(ESCROW_PURSE), acc, balance, None).unwrap_or_revert_with(ApiError::User(Err::BadEscrow as u16));
+}
+
+/// Get an account's escrow: (balance, reward per approving guardian)
+#[no_mangle]
+pub extern "C" fn get_escrow() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let balance: U512 = read(&format!("eb{:?}", acc)).unwrap_or_default();
+    let reward: U512 = read(&format!("er{:?}", acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((balance, reward)).unwrap_or_revert());
+}
+
+/// Owner cancels a recovery on their account; its bond is forfeited
+#[no_mangle]
+pub extern "C" fn cancel() {
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = recovery_account_caller(id);
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    if frozen(acc) { runtime::revert(ApiError::User(Err::AlreadyFrozen as u16)); }
+
+    close(acc, id);
+    settle_bond(acc, id, false);
+}
+
+/// Guardian rejects a recovery; once rejections reach the rejection threshold it is closed and its bond forfeited
+#[no_mangle]
+pub extern "C" fn reject() {
+    let id: U256 = runtime::get_named_arg("id");
+    let caller = caller_key();
+
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if !voters(id, acc).contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+
+    let rk = format!("rq{}_{}", id, gid(&caller));
+    if read::<bool>(&rk).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyRejected as u16)); }
+    if read::<bool>(&format!("rp{}_{}", id, gid(&caller))).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+
+    write(&rk, true);
+    let cnt: u8 = read(&format!("rj{}", id)).unwrap_or(0) + 1;
+    write(&format!("rj{}", id), cnt);
+
+    if cnt >= reject_threshold(id, acc) {
+        close(acc, id);
+        settle_bond(acc, id, false);
+    }
+}
+
+/// Close a proposal that expired without being finalized; its bond is refunded
+#[no_mangle]
+pub extern "C" fn expire() {
+    let id: U256 = runtime::get_named_arg("id");
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if !is_open(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    if !is_expired(id) { runtime::revert(ApiError::User(Err::NotExpired as u16)); }
+
+    close(acc, id);
+    settle_bond(acc, id, true);
+}
+
+/// Get the bond posted for a recovery: (initiator, amount), if any
+#[no_mangle]
+pub extern "C" fn get_bond() {
+    let id: U256 = runtime::get_named_arg("id");
+    let bond: Option<(AccountHash, U512)> = read(&format!("rb{}", id));
+    runtime::ret(CLValue::from_t(bond).unwrap_or_revert());
+}
+
+/// Get the open (competing) recovery proposals for an account
+#[no_mangle]
+pub extern "C" fn get_recoveries_for_account() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let open: Vec<U256> = read(&format!("ap{:?}", acc)).unwrap_or(vec![]);
+    runtime::ret(CLValue::from_t(open).unwrap_or_revert());
+}
+
+/// Choose who may start a recovery: guardians (0), a designated initiator (1), anyone posting a bond (2)
+/// or anyone (3, the default)
+#[no_mangle]
+pub extern "C" fn set_initiator_policy() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let policy: u8 = runtime::get_named_arg("policy");
+    let initiator: Option<AccountHash> = runtime::get_named_arg("initiator");
+    let bond: Option<U512> = runtime::try_get_named_arg("bond");
+
+    unfrozen_owner(acc);
+    if policy > POLICY_ANYONE || (policy == POLICY_INITIATOR && initiator.is_none()) { runtime::revert(ApiError::User(Err::BadPolicy as u16)); }
+    if policy == POLICY_BOND && bond.unwrap_or_default().is_zero() { runtime::revert(ApiError::User(Err::BadBond as u16)); }
+
+    write(&format!("ip{:?}", acc), policy);
+    if let Some(i) = initiator { write(&format!("ii{:?}", acc), i); }
+    if let Some(b) = bond { write(&format!("ib{:?}", acc), b); }
+}
+
+/// Get an account's initiator policy, designated initiator and bond amount
+#[no_mangle]
+pub extern "C" fn get_initiator_policy() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let policy: u8 = read(&format!("ip{:?}", acc)).unwrap_or(POLICY_ANYONE);
+    let initiator: Option<AccountHash> = read(&format!("ii{:?}", acc));
+    let bond: U512 = read(&format!("ib{:?}", acc)).unwrap_or_default();
+    runtime::ret(CLValue::from_t((policy, initiator, bond)).unwrap_or_revert());
+}
+
+/// Set how long a soft-retired key stays associated before it can be removed
+#[no_mangle]
+pub extern "C" fn set_retirement_grace() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let period: u64 = runtime::get_named_arg("period");
+    unfrozen_owner(acc);
+    write(&format!("gp{:?}", acc), period);
+}
+
+/// Owner records the account's key configuration: the key the owner signs with, its weight and
+/// the deployment and key management thresholds. Rotations keep it current; it is what a soft
+/// retirement restores on rollback.
+#[no_mangle]
+pub extern "C" fn set_key_config() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let owner_key: AccountHash = runtime::get_named_arg("owner_key");
+    unfrozen_owner(acc);
+    let config = key_config_args(owner_key).unwrap_or_revert_with(ApiError::MissingArgument);
+    write(&format!("kc{:?}", acc), config);
+}
+
+/// Get an account's recorded key configuration: (owner key, (weight, deployment, key management))
+#[no_mangle]
+pub extern "C" fn get_key_config() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    runtime::ret(CLValue::from_t(read::<KeyConfig>(&format!("kc{:?}", acc))).unwrap_or_revert());
+}
+
+/// Record a rotation, called by the rotation WASM in the recovered account's context. The new
+/// keys are the recovery's targets and the old key must be the recorded owner key. A soft
+/// retirement keeps the recorded pre-recovery configuration so `rollback` can restore it. Returns
+/// the targets `(account hash, weight)` the WASM must add and the recorded (deployment, key
+/// management) thresholds from before the rotation, if known, for its receipt.
+#[no_mangle]
+pub extern "C" fn record_rotation() {
+    let id: U256 = runtime::get_named_arg("id");
+    let old_key: AccountHash = runtime::get_named_arg("old_key");
+    let dep: u8 = runtime::get_named_arg("deployment_threshold");
+    let km: u8 = runtime::get_named_arg("key_management_threshold");
+    let soft_retire: bool = runtime::get_named_arg("soft_retire");
+
+    let acc = recovery_account_caller(id);
+    // Finalized proposals stay recordable, since the rotation deploy usually follows `finalize`
+    if is_closed(id) { runtime::revert(ApiError::User(Err::Closed as u16)); }
+    if !approved(id) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
+    let rk = format!("xr{}", id);
+    if read::<bool>(&rk).unwrap_or(false) { runtime::revert(ApiError::User(Err::RetirementExists as u16)); }
+    write(&rk, true);
+
+    let targets: Vec<(AccountHash, u8)> = read::<Vec<(PublicKey, u8)>>(&format!("rt{}", id))
+        .unwrap_or_revert_with(ApiError::User(Err::NotFound as u16))
+        .iter()
+        .map(|(pk, w)| (AccountHash::from(pk), *w))
+        .collect();
+    let ck = format!("kc{:?}", acc);
+    let previous: Option<KeyConfig> = read(&ck);
+    if previous.is_some_and(|(owner_key, _)| owner_key != old_key) { runtime::revert(ApiError::User(Err::WrongKey as u16)); }
+
+    if soft_retire {
+        let (_, config) = previous.unwrap_or_revert_with(ApiError::User(Err::NoKeyConfig as u16));
+        let grace: u64 = read(&format!("gp{:?}", acc)).unwrap_or(DEFAULT_RETIREMENT_GRACE);
+        write(&format!("xo{}", id), old_key);
+        write(&format!("xc{}", id), config);
+        write(&format!("xn{}", id), targets.iter().map(|(k, _)| *k).collect::<Vec<AccountHash>>());
+        write(&format!("xe{}", id), now() + grace);
+        write(&format!("xs{}", id), RETIREMENT_PENDING);
+    }
+    let (new_owner, weight) = *targets.first().unwrap_or_revert_with(ApiError::User(Err::BadTargets as u16));
+    write(&ck, (new_owner, (weight, dep, km)));
+    let previous_thresholds = previous.map(|(_, (_, dep, km))| (dep, km));
+    runtime::ret(CLValue::from_t((targets, previous_thresholds)).unwrap_or_revert());
+}
+
+/// Approve removal of a soft-retired key once the grace period is over, unless a rollback was
+/// authorized. Returns the key to remove.
+#[no_mangle]
+pub extern "C" fn finalize_retirement() {
+    let id: U256 = runtime::get_named_arg("id");
+    recovery_account_caller(id);
+    if now() < pending_retirement_end(id) { runtime::revert(ApiError::User(Err::GracePeriodActive as u16)); }
+
+    write(&format!("xs{}", id), RETIREMENT_DONE);
+    let old_key: AccountHash = read(&format!("xo{}", id)).unwrap_or_revert_with(ApiError::User(Err::NoRetirement as u16));
+    runtime::ret(CLValue::from_t(old_key).unwrap_or_revert());
+}
+
+/// Holder of a soft-retired key authorizes rolling the recovery back during the grace period.
+/// Anyone may submit the old key's signature over the rollback message; the key itself has no
+/// weight left to send deploys.
+#[no_mangle]
+pub extern "C" fn authorize_rollback() {
+    let id: U256 = runtime::get_named_arg("id");
+    let public_key: PublicKey = runtime::get_named_arg("public_key");
+    let signature: Bytes = runtime::get_named_arg("signature");
+
+    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    if now() >= pending_retirement_end(id) { runtime::revert(ApiError::User(Err::GracePeriodOver as u16)); }
+    let old_key: AccountHash = read(&format!("xo{}", id)).unwrap_or_revert_with(ApiError::User(Err::NoRetirement as u16));
+    if AccountHash::from(&public_key) != old_key { runtime::revert(ApiError::User(Err::WrongKey as u16)); }
+
+    let msg = RollbackMessage { chain_name: chain_name(), registry: registry_hash(), recovery_id: id, account: acc };
+    cryptography::verify_signature(msg.signed_bytes().unwrap_or_revert(), &parse_signature(&signature), &public_key)
+        .unwrap_or_revert_with(ApiError::User(Err::BadSignature as u16));
+    write(&format!("xs{}", id), RETIREMENT_ROLLBACK_AUTHORIZED);
+}
+
+/// Approve restoring the pre-recovery key configuration once the old key authorized it.
+/// Returns (old_key, (old_key_weight, deployment_threshold, key_management_threshold), new_keys).
+#[no_mangle]
+pub extern "C" fn rollback() {
+    let id: U256 = runtime::get_named_arg("id");
+    let acc = recovery_account_caller(id);
+    if read::<u8>(&format!("xs{}", id)) != Some(RETIREMENT_ROLLBACK_AUTHORIZED) { runtime::revert(ApiError::User(Err::RollbackNotAuthorized as u16)); }
+
+    write(&format!("xs{}", id), RETIREMENT_ROLLED_BACK);
+    let old_key: AccountHash = read(&format!("xo{}", id)).unwrap_or_revert_with(ApiError::User(Err::NoRetirement as u16));
+    let config: (u8, u8, u8) = read(&format!("xc{}", id)).unwrap_or_revert_with(ApiError::User(Err::NoRetirement as u16));
+    let new_keys: Vec<AccountHash> = read(&format!("xn{}", id)).unwrap_or(vec![]);
+    write(&format!("kc{:?}", acc), (old_key, config));
+    runtime::ret(CLValue::from_t((old_key, config, new_keys)).unwrap_or_revert());
+}
+
+/// Get the status of a soft retirement: (status, grace period end), status 0 if none
+#[no_mangle]
+pub extern "C" fn get_retirement() {
+    let id: U256 = runtime::get_named_arg("id");
+    let status: u8 = read(&format!("xs{}", id)).unwrap_or(0);
+    let end: u64 = read(&format!("xe{}", id)).unwrap_or(0);
+    runtime::ret(CLValue::from_t((status, end)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_guardians() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    if !read::<bool>(&format!("i{:?}", acc)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotInit as u16)); }
+    runtime::ret(CLValue::from_t(guardians(acc)).unwrap_or_revert());
+}
 
-    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
-    let guards: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NotGuardian as u16));
+/// Guardian moves to a new account: every account they protect or were nominated by (including
+/// pending guardian changes and groups), their freeze and guardian-change votes, drill responses,
+/// open approvals and rejections, and both reverse indexes are rewritten to `new_guardian`.
+/// A new account's key must also sign the deploy. A contract guardian migrates by calling from
+/// the old contract, whose own governance authorizes the move.
+#[no_mangle]
+pub extern "C" fn migrate_guardian() {
+    let old = caller_key();
+    let new: Key = match runtime::try_get_named_arg::<AccountHash>("new_guardian") {
+        Some(a) => Key::Account(a),
+        None => runtime::get_named_arg("new_guardian"),
+    };
+    let (old_id, new_id) = (gid(&old), gid(&new));
 
-    if !guards.contains(&caller) { runtime::revert(ApiError::User(Err::NotGuardian as u16)); }
+    if new == old || !matches!(new, Key::Account(_) | Key::Hash(_)) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+    if new.into_account().is_some_and(|a| !runtime::list_authorization_keys().contains(&a)) { runtime::revert(ApiError::User(Err::NotCoSigned as u16)); }
 
-    let ak = format!("rp{}_{:?}", id, caller);
-    if read::<bool>(&ak).unwrap_or(false) { runtime::revert(ApiError::User(Err::AlreadyApproved as u16)); }
+    let protected: Vec<AccountHash> = read(&format!("ga{}", old_id)).unwrap_or(vec![]);
+    let nominated: Vec<AccountHash> = read(&format!("gn{}", old_id)).unwrap_or(vec![]);
+    for acc in protected.iter().chain(nominated.iter()) {
+        let gk = format!("g{:?}", acc);
+        let pk = format!("pn{:?}", acc);
+        if [&gk, &pk].iter().any(|k| read_guardians(k).unwrap_or_default().contains(&new)) { runtime::revert(ApiError::User(Err::BadGuardians as u16)); }
+        for key in [gk, pk, format!("gk{:?}", acc), format!("gv{:?}", acc)] {
+            replace_guardian(&key, old, new);
+        }
+        let fk = format!("fv{:?}", acc);
+        if let Some(mut votes) = read::<Vec<(Key, u64)>>(&fk) {
+            for (v, _) in votes.iter_mut().filter(|(v, _)| *v == old) { *v = new; }
+            write(&fk, votes);
+        }
+        // Drill reports list the current guardians, so past responses move with the guardian
+        for id in read::<Vec<U256>>(&format!("dr{:?}", acc)).unwrap_or_default() {
+            if let Some(response) = read::<u64>(&format!("rr{}_{}", id, old_id)) {
+                write(&format!("rr{}_{}", id, new_id), response);
+            }
+        }
+        for gg in [format!("gg{:?}", acc), format!("pg{:?}", acc)] {
+            let Some(mut groups) = read::<Vec<GuardianGroup>>(&gg) else { continue };
+            for m in groups.iter_mut().flat_map(|(members, _)| members.iter_mut()).filter(|m| **m == old) { *m = new; }
+            write(&gg, groups);
+        }
+    }
 
-    write(&ak, true);
-    let cnt: u8 = read(&format!("rc{}", id)).unwrap_or(0) + 1;
-    write(&format!("rc{}", id), cnt);
+    // Carry over votes on every open proposal and drill of those accounts; the guardian's own
+    // recovery index misses proposals started before they accepted
+    let open = protected.iter().chain(nominated.iter()).map(|acc| {
+        let mut ids: Vec<U256> = read(&format!("ap{:?}", acc)).unwrap_or_default();
+        ids.extend(read::<Vec<U256>>(&format!("dr{:?}", acc)).unwrap_or_default().into_iter().filter(|id| is_open(*id)));
+        ids
+    });
+    for id in migrated_proposals(open) {
+        for prefix in ["rp", "rq"] {
+            let ok = format!("{}{}_{}", prefix, id, old_id);
+            if read::<bool>(&ok).unwrap_or(false) {
+                write(&ok, false);
+                write(&format!("{}{}_{}", prefix, id, new_id), true);
+            }
+        }
+    }
 
-    let thresh: u8 = read(&format!("t{:?}", acc)).unwrap_or(2);
-    if cnt >= thresh { write(&format!("ro{}", id), true); }
+    move_list::<AccountHash>(&format!("ga{}", old_id), &format!("ga{}", new_id));
+    move_list::<AccountHash>(&format!("gn{}", old_id), &format!("gn{}", new_id));
+    move_list::<U256>(&format!("gr{}", old_id), &format!("gr{}", new_id));
+    write(&format!("hb{}", new_id), now());
 }
 
+/// Guardian signals that they still control their key
 #[no_mangle]
-pub extern "C" fn is_approved() {
-    let id: U256 = runtime::get_named_arg("id");
-    runtime::ret(CLValue::from_t(read::<bool>(&format!("ro{}", id)).unwrap_or(false)).unwrap_or_revert());
+pub extern "C" fn heartbeat() {
+    record_heartbeat(&caller_key());
 }
 
+/// Get each accepted guardian's last heartbeat (0 if never), whether the live ones alone could
+/// approve a recovery (meet the threshold, or satisfy the guardian groups when the account has
+/// them) and the number of unused recovery codes. Codes show no sign of life and are not counted
+/// as live, since nothing on-chain says whether they were kept.
 #[no_mangle]
-pub extern "C" fn finalize() {
-    let id: U256 = runtime::get_named_arg("id");
-    if !read::<bool>(&format!("ro{}", id)).unwrap_or(false) { runtime::revert(ApiError::User(Err::NotApproved as u16)); }
-
-    // Get the target account for this recovery
-    let acc: AccountHash = read(&format!("ra{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
-    
-    // Remove this recovery ID from each guardian's active recoveries list
-    let guards: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or(vec![]);
-    for guard in &guards {
-        let key = format!("gr{:?}", guard);
-        let mut recoveries: Vec<U256> = read(&key).unwrap_or(vec![]);
-        recoveries.retain(|&r| r != id);
-        write(&key, recoveries);
-    }
+pub extern "C" fn get_guardian_health() {
+    let acc: AccountHash = runtime::get_named_arg("account");
+    let t = now();
+    let seen: Vec<(Key, u64)> = guardians(acc).into_iter()
+        .map(|g| (g, read::<u64>(&format!("hb{}", gid(&g))).unwrap_or(0)))
+        .collect();
+    let live: Vec<Key> = seen.iter().filter(|(_, last)| *last > 0 && t < last + LIVENESS_WINDOW).map(|(g, _)| *g).collect();
+    let codes: Vec<[u8; 32]> = read(&format!("gh{:?}", acc)).unwrap_or_default();
 
-    // Clear the active recovery mapping for this account
-    // Note: Casper dictionary doesn't have delete, so we write a zero value
-    // The active recovery check in start_recovery uses is_some(), so we need to handle this
-    // For now, we mark it as finalized by setting a special flag
-    write(&format!("rf{}", id), true); // Recovery finalized flag
+    let groups: Vec<GuardianGroup> = read(&format!("gg{:?}", acc)).unwrap_or_default();
+    let healthy = if groups.is_empty() {
+        let thresh: u8 = read(&format!("t{:?}", acc)).unwrap_or(2);
+        live.len() >= thresh as usize
+    } else {
+        groups_satisfied(&groups, read(&format!("go{:?}", acc)).unwrap_or(1), |g| live.contains(g))
+    };
+    runtime::ret(CLValue::from_t((seen, healthy, codes.len() as u32)).unwrap_or_revert());
 }
 
+/// Get the guardians of an account who have not yet accepted their nomination
 #[no_mangle]
-pub extern "C" fn get_guardians() {
+pub extern "C" fn get_pending_guardians() {
     let acc: AccountHash = runtime::get_named_arg("account");
-    let g: Vec<AccountHash> = read(&format!("g{:?}", acc)).unwrap_or_revert_with(ApiError::User(Err::NotInit as u16));
-    runtime::ret(CLValue::from_t(g).unwrap_or_revert());
+    let accepted = guardians(acc);
+    let mut pending = read_guardians(&format!("g{:?}", acc)).unwrap_or_default();
+    pending.retain(|g| !accepted.contains(g));
+    runtime::ret(CLValue::from_t(pending).unwrap_or_revert());
 }
 
 #[no_mangle]
@@ -185,19 +1620,35 @@ pub extern "C" fn init_storage() {
     storage::new_dictionary(DICT).unwrap_or_revert();
 }
 
+/// Get the (key, weight) targets a recovery rotates the account to
+#[no_mangle]
+pub extern "C" fn get_recovery_targets() {
+    let id: U256 = runtime::get_named_arg("id");
+    let t: Vec<(PublicKey, u8)> = read(&format!("rt{}", id)).unwrap_or_revert_with(ApiError::User(Err::NotFound as u16));
+    runtime::ret(CLValue::from_t(t).unwrap_or_revert());
+}
+
 /// Get all active recovery IDs for a guardian
 #[no_mangle]
 pub extern "C" fn get_recoveries_for_guardian() {
-    let guardian: AccountHash = runtime::get_named_arg("guardian");
-    let recoveries: Vec<U256> = read(&format!("gr{:?}", guardian)).unwrap_or(vec![]);
+    let guardian = guardian_arg();
+    let recoveries: Vec<U256> = read(&format!("gr{}", gid(&guardian))).unwrap_or(vec![]);
     runtime::ret(CLValue::from_t(recoveries).unwrap_or_revert());
 }
 
+/// Get all accounts that have nominated a guardian and are waiting for an answer
+#[no_mangle]
+pub extern "C" fn get_nominations() {
+    let guardian = guardian_arg();
+    let accounts: Vec<AccountHash> = read(&format!("gn{}", gid(&guardian))).unwrap_or(vec![]);
+    runtime::ret(CLValue::from_t(accounts).unwrap_or_revert());
+}
+
 /// Get all accounts that a guardian protects
 #[no_mangle]
 pub extern "C" fn get_protected_accounts() {
-    let guardian: AccountHash = runtime::get_named_arg("guardian");
-    let accounts: Vec<AccountHash> = read(&format!("ga{:?}", guardian)).unwrap_or(vec![]);
+    let guardian = guardian_arg();
+    let accounts: Vec<AccountHash> = read(&format!("ga{}", gid(&guardian))).unwrap_or(vec![]);
     runtime::ret(CLValue::from_t(accounts).unwrap_or_revert());
 }
 
@@ -215,8 +1666,20 @@ pub extern "C" fn call() {
         "init_guardians",
         vec![
             Parameter::new("account", CLType::ByteArray(32)),
-            Parameter::new("guardians", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("guardians", CLType::List(Box::new(CLType::Key))),
             Parameter::new("threshold", CLType::U8),
+            Parameter::new("rejection_threshold", CLType::U8),
+            Parameter::new("guardian_change_threshold", CLType::U8),
+            Parameter::new("freeze_threshold", CLType::U8),
+            Parameter::new("groups", CLType::List(Box::new(CLType::Tuple2([
+                Box::new(CLType::List(Box::new(CLType::Key))),
+                Box::new(CLType::U8),
+            ])))),
+            Parameter::new("group_threshold", CLType::U8),
+            Parameter::new("recovery_codes", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("owner_key_weight", CLType::U8),
+            Parameter::new("deployment_threshold", CLType::U8),
+            Parameter::new("key_management_threshold", CLType::U8),
         ],
         CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
     ));
@@ -226,6 +1689,8 @@ pub extern "C" fn call() {
         vec![
             Parameter::new("account", CLType::ByteArray(32)),
             Parameter::new("new_key", CLType::PublicKey),
+            Parameter::new("new_keys", CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::U8)])))),
+            Parameter::new("purse", CLType::URef),
         ],
         CLType::U256, EntryPointAccess::Public, EntryPointType::Called,
     ));
@@ -235,19 +1700,204 @@ pub extern "C" fn call() {
         CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
     ));
 
+    eps.add_entry_point(EntryPoint::new(
+        "revoke", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "cancel", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "reject", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "expire", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "deposit_escrow",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("purse", CLType::URef),
+            Parameter::new("amount", CLType::U512),
+            Parameter::new("reward", CLType::U512),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "withdraw_escrow", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_escrow", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U512)]), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_bond", vec![Parameter::new("id", CLType::U256)],
+        CLType::Option(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::U512)]))),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
     eps.add_entry_point(EntryPoint::new(
         "is_approved", vec![Parameter::new("id", CLType::U256)],
         CLType::Bool, EntryPointAccess::Public, EntryPointType::Called,
     ));
 
+    eps.add_entry_point(EntryPoint::new(
+        "approve_with_signatures",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("approvals", CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::List(Box::new(CLType::U8)))])))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "approve_with_code",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("code_key", CLType::PublicKey),
+            Parameter::new("signature", CLType::List(Box::new(CLType::U8))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recovery_code_count", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U32, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "start_drill", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U256, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "end_drill", vec![Parameter::new("id", CLType::U256)],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_drills", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::U256)), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_drill_report", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple2([
+            Box::new(CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::Option(Box::new(CLType::U64)))])))),
+            Box::new(CLType::Bool),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_inheritance",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("beneficiary", CLType::PublicKey),
+            Parameter::new("beneficiaries", CLType::List(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("threshold", CLType::U8),
+            Parameter::new("period", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "ping", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "start_inheritance", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::U256, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "vote_freeze", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "unfreeze", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_freeze", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([Box::new(CLType::Bool), Box::new(CLType::U64), Box::new(CLType::U8)]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_beneficiary", vec![Parameter::new("id", CLType::U256)],
+        CLType::ByteArray(32), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_inheritance", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([
+            Box::new(CLType::Option(Box::new(CLType::PublicKey))),
+            Box::new(CLType::List(Box::new(CLType::ByteArray(32)))),
+            Box::new(CLType::U8),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_inheritance_status", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([Box::new(CLType::U64), Box::new(CLType::U64), Box::new(CLType::Bool)]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_backup_key",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("backup_key", CLType::Option(Box::new(CLType::PublicKey))),
+            Parameter::new("delay", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_backup_key", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([Box::new(CLType::Option(Box::new(CLType::ByteArray(32)))), Box::new(CLType::U64)]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_backup_approval", vec![Parameter::new("id", CLType::U256)],
+        CLType::U64, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_approval_nonce", vec![Parameter::new("id", CLType::U256)],
+        CLType::U64, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
     eps.add_entry_point(EntryPoint::new(
         "finalize", vec![Parameter::new("id", CLType::U256)],
         CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
     ));
 
+    eps.add_entry_point(EntryPoint::new(
+        "get_recoveries_for_account", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::U256)), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
     eps.add_entry_point(EntryPoint::new(
         "get_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
-        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, EntryPointType::Called,
+        CLType::List(Box::new(CLType::Key)), EntryPointAccess::Public, EntryPointType::Called,
     ));
 
     eps.add_entry_point(EntryPoint::new(
@@ -256,18 +1906,216 @@ pub extern "C" fn call() {
     ));
 
     eps.add_entry_point(EntryPoint::new(
-        "get_recoveries_for_guardian", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        "get_recovery_targets", vec![Parameter::new("id", CLType::U256)],
+        CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::PublicKey), Box::new(CLType::U8)]))), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_recoveries_for_guardian", vec![Parameter::new("guardian", CLType::Key)],
         CLType::List(Box::new(CLType::U256)), EntryPointAccess::Public, EntryPointType::Called,
     ));
 
     eps.add_entry_point(EntryPoint::new(
-        "get_protected_accounts", vec![Parameter::new("guardian", CLType::ByteArray(32))],
+        "get_protected_accounts", vec![Parameter::new("guardian", CLType::Key)],
+        CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "accept_guardianship", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "decline_guardianship", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "propose_guardians",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("guardians", CLType::List(Box::new(CLType::Key))),
+            Parameter::new("threshold", CLType::U8),
+            Parameter::new("rejection_threshold", CLType::U8),
+            Parameter::new("guardian_change_threshold", CLType::U8),
+            Parameter::new("freeze_threshold", CLType::U8),
+            Parameter::new("groups", CLType::List(Box::new(CLType::Tuple2([
+                Box::new(CLType::List(Box::new(CLType::Key))),
+                Box::new(CLType::U8),
+            ])))),
+            Parameter::new("group_threshold", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "approve_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardian_proposal", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([
+            Box::new(CLType::List(Box::new(CLType::Key))),
+            Box::new(CLType::List(Box::new(CLType::U8))),
+            Box::new(CLType::U8),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardian_groups", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple2([
+            Box::new(CLType::List(Box::new(CLType::Tuple2([
+                Box::new(CLType::List(Box::new(CLType::Key))),
+                Box::new(CLType::U8),
+            ])))),
+            Box::new(CLType::U8),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_thresholds", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::U8)), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_pending_guardians", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::List(Box::new(CLType::Key)), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "migrate_guardian", vec![Parameter::new("new_guardian", CLType::Key)],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "heartbeat", vec![],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_guardian_health", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([
+            Box::new(CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::Key), Box::new(CLType::U64)])))),
+            Box::new(CLType::Bool),
+            Box::new(CLType::U32),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_nominations", vec![Parameter::new("guardian", CLType::Key)],
         CLType::List(Box::new(CLType::ByteArray(32))), EntryPointAccess::Public, EntryPointType::Called,
     ));
 
+    eps.add_entry_point(EntryPoint::new(
+        "set_initiator_policy",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("policy", CLType::U8),
+            Parameter::new("initiator", CLType::Option(Box::new(CLType::ByteArray(32)))),
+            Parameter::new("bond", CLType::U512),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_initiator_policy", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Tuple3([Box::new(CLType::U8), Box::new(CLType::Option(Box::new(CLType::ByteArray(32)))), Box::new(CLType::U512)]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_retirement_grace",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("period", CLType::U64),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "set_key_config",
+        vec![
+            Parameter::new("account", CLType::ByteArray(32)),
+            Parameter::new("owner_key", CLType::ByteArray(32)),
+            Parameter::new("owner_key_weight", CLType::U8),
+            Parameter::new("deployment_threshold", CLType::U8),
+            Parameter::new("key_management_threshold", CLType::U8),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_key_config", vec![Parameter::new("account", CLType::ByteArray(32))],
+        CLType::Option(Box::new(CLType::Tuple2([
+            Box::new(CLType::ByteArray(32)),
+            Box::new(CLType::Tuple3([Box::new(CLType::U8), Box::new(CLType::U8), Box::new(CLType::U8)])),
+        ]))),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "record_rotation",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("old_key", CLType::ByteArray(32)),
+            Parameter::new("deployment_threshold", CLType::U8),
+            Parameter::new("key_management_threshold", CLType::U8),
+            Parameter::new("soft_retire", CLType::Bool),
+        ],
+        CLType::Tuple2([
+            Box::new(CLType::List(Box::new(CLType::Tuple2([Box::new(CLType::ByteArray(32)), Box::new(CLType::U8)])))),
+            Box::new(CLType::Option(Box::new(CLType::Tuple2([Box::new(CLType::U8), Box::new(CLType::U8)])))),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "authorize_rollback",
+        vec![
+            Parameter::new("id", CLType::U256),
+            Parameter::new("public_key", CLType::PublicKey),
+            Parameter::new("signature", CLType::List(Box::new(CLType::U8))),
+        ],
+        CLType::Unit, EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "finalize_retirement", vec![Parameter::new("id", CLType::U256)],
+        CLType::ByteArray(32), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "rollback", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple3([
+            Box::new(CLType::ByteArray(32)),
+            Box::new(CLType::Tuple3([Box::new(CLType::U8), Box::new(CLType::U8), Box::new(CLType::U8)])),
+            Box::new(CLType::List(Box::new(CLType::ByteArray(32)))),
+        ]),
+        EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    eps.add_entry_point(EntryPoint::new(
+        "get_retirement", vec![Parameter::new("id", CLType::U256)],
+        CLType::Tuple2([Box::new(CLType::U8), Box::new(CLType::U64)]), EntryPointAccess::Public, EntryPointType::Called,
+    ));
+
+    // Forfeited bonds go to the optional treasury purse instead of the protected account
+    let mut named_keys = NamedKeys::new();
+    if let Some(treasury) = runtime::try_get_named_arg::<URef>(TREASURY) {
+        named_keys.insert(TREASURY.to_string(), Key::URef(treasury.into_add()));
+    }
+    // Network name bound into off-chain approval messages
+    if let Some(chain_name) = runtime::try_get_named_arg::<String>(CHAIN_NAME) {
+        named_keys.insert(CHAIN_NAME.to_string(), storage::new_uref(chain_name).into());
+    }
+
     let (hash, _) = storage::new_locked_contract(
         eps.into(), 
-        None, 
+        Some(named_keys), 
         Some("recovery_registry_contract".to_string()), 
         Some("recovery_registry_package".to_string()),
         None
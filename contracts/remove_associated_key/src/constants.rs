@@ -1 +1,2 @@
 pub const RUNTIME_ARG_REMOVE_ASSOCIATED_KEY: &str = "remove_key";
+pub const RUNTIME_ARG_ALLOW_MISSING: &str = "allow_missing";
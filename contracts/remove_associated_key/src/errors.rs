@@ -1,9 +1,26 @@
+use casper_types::account::RemoveKeyFailure;
 use casper_types::ApiError;
 
 #[repr(u16)]
 #[derive(Clone, Copy)]
 pub enum UserError {
     InvalidAccount,
+    MissingKey,
+    PermissionDenied,
+    ThresholdViolation,
+    /// Failure variant introduced by a newer `casper-types`
+    UnknownFailure,
+}
+
+impl From<RemoveKeyFailure> for UserError {
+    fn from(failure: RemoveKeyFailure) -> Self {
+        match failure {
+            RemoveKeyFailure::MissingKey => UserError::MissingKey,
+            RemoveKeyFailure::PermissionDenied => UserError::PermissionDenied,
+            RemoveKeyFailure::ThresholdViolation => UserError::ThresholdViolation,
+            _ => UserError::UnknownFailure,
+        }
+    }
 }
 
 impl From<UserError> for ApiError {
@@ -5,13 +5,21 @@
 compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
 
 use casper_contract::contract_api::{account, runtime};
-use casper_types::Key;
-use remove_associated_key::constants::RUNTIME_ARG_REMOVE_ASSOCIATED_KEY;
+use casper_types::account::RemoveKeyFailure;
+use guardian_types::account_arg;
+use remove_associated_key::constants::{
+    RUNTIME_ARG_ALLOW_MISSING, RUNTIME_ARG_REMOVE_ASSOCIATED_KEY,
+};
+use remove_associated_key::errors::UserError;
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let key_to_remove: Key = runtime::get_named_arg(RUNTIME_ARG_REMOVE_ASSOCIATED_KEY);
-    if let Key::Account(account) = key_to_remove {
-        let _ret = account::remove_associated_key(account);
+    let account = account_arg(RUNTIME_ARG_REMOVE_ASSOCIATED_KEY, UserError::InvalidAccount);
+    let allow_missing: bool = runtime::try_get_named_arg(RUNTIME_ARG_ALLOW_MISSING).unwrap_or(false);
+
+    match account::remove_associated_key(account) {
+        Ok(()) => {}
+        Err(RemoveKeyFailure::MissingKey) if allow_missing => {}
+        Err(failure) => runtime::revert(UserError::from(failure)),
     }
 }
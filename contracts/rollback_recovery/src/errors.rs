@@ -0,0 +1,18 @@
+use casper_types::ApiError;
+
+#[repr(u16)]
+#[derive(Clone, Copy)]
+pub enum UserError {
+    InvalidRegistry,
+    /// No longer raised: the registry checks the old key's rollback authorization
+    OldKeyNotSigning,
+    RestoreKeyFailed,
+    RestoreThresholdsFailed,
+    RemoveKeyFailed,
+}
+
+impl From<UserError> for ApiError {
+    fn from(error: UserError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
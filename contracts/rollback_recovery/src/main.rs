@@ -0,0 +1,67 @@
+//! Rollback Recovery Contract
+//!
+//! Undoes a soft-retirement recovery once the soft-retired old key authorized it
+//! in recovery_registry (`authorize_rollback`) during the grace period:
+//! 1. Asks the registry to approve the rollback, which returns the
+//!    pre-recovery configuration
+//! 2. Restores the old key's weight and the previous thresholds
+//! 3. Removes the keys the recovery added
+//!
+//! The old key has no weight while retired, so the deploy is signed by keys
+//! meeting the current key management threshold, e.g. the guardians.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use casper_contract::contract_api::{account, runtime};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::account::{AccountHash, RemoveKeyFailure, Weight};
+use casper_types::{runtime_args, Key, U256};
+use guardian_types::{registry_contract_hash, set_action_thresholds};
+use rollback_recovery::constants::{
+    REGISTRY_ARG_ID, REGISTRY_ENTRY_POINT_ROLLBACK, RUNTIME_ARG_RECOVERY_ID, RUNTIME_ARG_REGISTRY,
+};
+use rollback_recovery::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let recovery_id: U256 = runtime::get_named_arg(RUNTIME_ARG_RECOVERY_ID);
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    // Step 1: The registry reverts unless the old key authorized the rollback
+    let (old_key, (old_key_weight, deployment_threshold, key_management_threshold), new_keys): (
+        AccountHash,
+        (u8, u8, u8),
+        Vec<AccountHash>,
+    ) = runtime::call_contract(
+        registry_hash,
+        REGISTRY_ENTRY_POINT_ROLLBACK,
+        runtime_args! { REGISTRY_ARG_ID => recovery_id },
+    );
+
+    // Step 2: Restore the old key, then the thresholds
+    account::update_associated_key(old_key, Weight::new(old_key_weight))
+        .unwrap_or_revert_with(UserError::RestoreKeyFailed);
+
+    set_action_thresholds(
+        deployment_threshold,
+        key_management_threshold,
+        UserError::RestoreThresholdsFailed,
+    );
+
+    // Step 3: Remove the keys added by the recovery
+    for new_key in new_keys.iter().filter(|key| **key != old_key) {
+        match account::remove_associated_key(*new_key) {
+            Ok(()) | Err(RemoveKeyFailure::MissingKey) => {}
+            Err(_) => runtime::revert(UserError::RemoveKeyFailed),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+pub const RUNTIME_ARG_REGISTRY: &str = "registry";
+pub const RUNTIME_ARG_GUARDIANS: &str = "guardians";
+pub const RUNTIME_ARG_GUARDIAN_WEIGHTS: &str = "guardian_weights";
+pub const RUNTIME_ARG_PRIMARY_WEIGHT: &str = "primary_weight";
+pub const RUNTIME_ARG_THRESHOLD: &str = "threshold";
+pub const RUNTIME_ARG_DEPLOYMENT_THRESHOLD: &str = "deployment_threshold";
+pub const RUNTIME_ARG_KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
+pub const RUNTIME_ARG_ESCROW_AMOUNT: &str = "escrow_amount";
+pub const RUNTIME_ARG_GUARDIAN_REWARD: &str = "guardian_reward";
+pub const RUNTIME_ARG_RECOVERY_CODES: &str = "recovery_codes";
+
+pub const REGISTRY_ENTRY_POINT_INIT_GUARDIANS: &str = "init_guardians";
+pub const REGISTRY_ARG_ACCOUNT: &str = "account";
+pub const REGISTRY_ARG_GUARDIANS: &str = "guardians";
+pub const REGISTRY_ARG_THRESHOLD: &str = "threshold";
+pub const REGISTRY_ARG_RECOVERY_CODES: &str = "recovery_codes";
+pub const REGISTRY_ARG_OWNER_KEY_WEIGHT: &str = "owner_key_weight";
+pub const REGISTRY_ARG_DEPLOYMENT_THRESHOLD: &str = "deployment_threshold";
+pub const REGISTRY_ARG_KEY_MANAGEMENT_THRESHOLD: &str = "key_management_threshold";
+
+/// Named key recording the guardian keys added here, which `sync_guardians` diffs against
+pub const NAMED_KEY_SYNCED_GUARDIANS: &str = guardian_types::SYNCED_GUARDIANS_KEY;
+
+/// Weight of an account's primary key unless `primary_weight` changes it
+pub const DEFAULT_PRIMARY_WEIGHT: u8 = 1;
+
+pub const REGISTRY_ENTRY_POINT_DEPOSIT_ESCROW: &str = "deposit_escrow";
+pub const REGISTRY_ARG_PURSE: &str = "purse";
+pub const REGISTRY_ARG_AMOUNT: &str = "amount";
+pub const REGISTRY_ARG_REWARD: &str = "reward";
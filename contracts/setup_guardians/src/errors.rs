@@ -0,0 +1,19 @@
+use casper_types::ApiError;
+
+#[repr(u16)]
+#[derive(Clone, Copy)]
+pub enum UserError {
+    InvalidRegistry,
+    WeightsMismatch,
+    AddGuardianFailed,
+    UpdatePrimaryWeightFailed,
+    UpdateThresholdsFailed,
+    EscrowTransferFailed,
+    InvalidSyncedGuardians,
+}
+
+impl From<UserError> for ApiError {
+    fn from(error: UserError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
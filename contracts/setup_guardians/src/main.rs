@@ -0,0 +1,129 @@
+//! Guardian Onboarding Contract
+//!
+//! Sets up guardian protection for the signing account in a single deploy:
+//! 1. Adds each guardian as an associated key with its weight
+//! 2. Optionally updates the weight of the owner's primary key
+//! 3. Sets the key management and deployment thresholds
+//! 4. Registers the same guardians, threshold and any recovery code hashes in recovery_registry,
+//!    along with the primary key's weight and the thresholds so a soft-retirement
+//!    recovery can later be rolled back to them
+//! 5. Records the added guardians in the `sentinelx_synced_guardians` named key, so
+//!    `sync_guardians` later removes the ones that leave the registry
+//! 6. Optionally funds the registry's guardian reward escrow
+//!
+//! Any failure reverts the whole deploy, so the account is never left half configured.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use casper_contract::contract_api::{account, runtime, storage, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::account::{AccountHash, Weight};
+use casper_types::{runtime_args, Key, U512};
+use guardian_types::{registry_contract_hash, set_action_thresholds};
+use setup_guardians::constants::{
+    DEFAULT_PRIMARY_WEIGHT, NAMED_KEY_SYNCED_GUARDIANS, REGISTRY_ARG_ACCOUNT, REGISTRY_ARG_AMOUNT,
+    REGISTRY_ARG_DEPLOYMENT_THRESHOLD, REGISTRY_ARG_GUARDIANS, REGISTRY_ARG_KEY_MANAGEMENT_THRESHOLD,
+    REGISTRY_ARG_OWNER_KEY_WEIGHT, REGISTRY_ARG_PURSE, REGISTRY_ARG_RECOVERY_CODES,
+    REGISTRY_ARG_REWARD, REGISTRY_ARG_THRESHOLD, REGISTRY_ENTRY_POINT_DEPOSIT_ESCROW,
+    REGISTRY_ENTRY_POINT_INIT_GUARDIANS,
+    RUNTIME_ARG_DEPLOYMENT_THRESHOLD, RUNTIME_ARG_ESCROW_AMOUNT, RUNTIME_ARG_GUARDIANS,
+    RUNTIME_ARG_GUARDIAN_REWARD, RUNTIME_ARG_GUARDIAN_WEIGHTS,
+    RUNTIME_ARG_KEY_MANAGEMENT_THRESHOLD, RUNTIME_ARG_PRIMARY_WEIGHT, RUNTIME_ARG_RECOVERY_CODES,
+    RUNTIME_ARG_REGISTRY, RUNTIME_ARG_THRESHOLD,
+};
+use setup_guardians::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // 1. Get runtime arguments
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let guardians: Vec<AccountHash> = runtime::get_named_arg(RUNTIME_ARG_GUARDIANS);
+    let guardian_weights: Vec<u8> = runtime::get_named_arg(RUNTIME_ARG_GUARDIAN_WEIGHTS);
+    let primary_weight: Option<u8> = runtime::try_get_named_arg(RUNTIME_ARG_PRIMARY_WEIGHT);
+    let threshold: u8 = runtime::get_named_arg(RUNTIME_ARG_THRESHOLD);
+    let deployment_threshold: u8 = runtime::get_named_arg(RUNTIME_ARG_DEPLOYMENT_THRESHOLD);
+    let key_management_threshold: u8 =
+        runtime::get_named_arg(RUNTIME_ARG_KEY_MANAGEMENT_THRESHOLD);
+    let escrow_amount: Option<U512> = runtime::try_get_named_arg(RUNTIME_ARG_ESCROW_AMOUNT);
+    let recovery_codes: Vec<[u8; 32]> =
+        runtime::try_get_named_arg(RUNTIME_ARG_RECOVERY_CODES).unwrap_or_default();
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    if guardians.len() != guardian_weights.len() {
+        runtime::revert(UserError::WeightsMismatch);
+    }
+
+    let owner = runtime::get_caller();
+
+    // Step 1: Add each guardian as an associated key
+    for (guardian, weight) in guardians.iter().zip(guardian_weights.iter()) {
+        account::add_associated_key(*guardian, Weight::new(*weight))
+            .unwrap_or_revert_with(UserError::AddGuardianFailed);
+    }
+
+    // Step 2: Raise the primary key so the owner keeps control on their own
+    if let Some(weight) = primary_weight {
+        account::update_associated_key(owner, Weight::new(weight))
+            .unwrap_or_revert_with(UserError::UpdatePrimaryWeightFailed);
+    }
+
+    // Step 3: Update thresholds, in whichever order keeps key management >= deployment
+    set_action_thresholds(
+        deployment_threshold,
+        key_management_threshold,
+        UserError::UpdateThresholdsFailed,
+    );
+
+    // Step 4: Register the guardians with the recovery registry
+    // The registry sees this account as the caller, which satisfies its owner check
+    runtime::call_contract::<()>(
+        registry_hash,
+        REGISTRY_ENTRY_POINT_INIT_GUARDIANS,
+        runtime_args! {
+            REGISTRY_ARG_ACCOUNT => owner,
+            REGISTRY_ARG_GUARDIANS => guardians.clone(),
+            REGISTRY_ARG_THRESHOLD => threshold,
+            REGISTRY_ARG_RECOVERY_CODES => recovery_codes,
+            REGISTRY_ARG_OWNER_KEY_WEIGHT => primary_weight.unwrap_or(DEFAULT_PRIMARY_WEIGHT),
+            REGISTRY_ARG_DEPLOYMENT_THRESHOLD => deployment_threshold,
+            REGISTRY_ARG_KEY_MANAGEMENT_THRESHOLD => key_management_threshold,
+        },
+    );
+
+    // Step 5: Remember the guardian keys applied, as sync_guardians does
+    match runtime::get_key(NAMED_KEY_SYNCED_GUARDIANS) {
+        Some(key) => storage::write(
+            key.into_uref()
+                .unwrap_or_revert_with(UserError::InvalidSyncedGuardians),
+            guardians,
+        ),
+        None => runtime::put_key(NAMED_KEY_SYNCED_GUARDIANS, storage::new_uref(guardians).into()),
+    }
+
+    // Step 6: Fund the guardian reward escrow
+    if let Some(amount) = escrow_amount {
+        let guardian_reward: U512 = runtime::get_named_arg(RUNTIME_ARG_GUARDIAN_REWARD);
+        let escrow_purse = system::create_purse();
+        system::transfer_from_purse_to_purse(account::get_main_purse(), escrow_purse, amount, None)
+            .unwrap_or_revert_with(UserError::EscrowTransferFailed);
+
+        runtime::call_contract::<()>(
+            registry_hash,
+            REGISTRY_ENTRY_POINT_DEPOSIT_ESCROW,
+            runtime_args! {
+                REGISTRY_ARG_ACCOUNT => owner,
+                REGISTRY_ARG_PURSE => escrow_purse,
+                REGISTRY_ARG_AMOUNT => amount,
+                REGISTRY_ARG_REWARD => guardian_reward,
+            },
+        );
+    }
+}
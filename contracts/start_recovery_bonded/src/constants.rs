@@ -0,0 +1,8 @@
+pub const RUNTIME_ARG_REGISTRY: &str = "registry";
+pub const RUNTIME_ARG_ACCOUNT: &str = "account";
+pub const RUNTIME_ARG_NEW_KEY: &str = "new_key";
+pub const RUNTIME_ARG_NEW_KEYS: &str = "new_keys";
+pub const RUNTIME_ARG_AMOUNT: &str = "amount";
+
+pub const REGISTRY_ENTRY_POINT_START_RECOVERY: &str = "start_recovery";
+pub const REGISTRY_ARG_PURSE: &str = "purse";
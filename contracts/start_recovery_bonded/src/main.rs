@@ -0,0 +1,66 @@
+//! Bonded Recovery Start Contract
+//!
+//! Starts a recovery for an account whose initiator policy requires a bond:
+//! 1. Moves `amount` CSPR from the caller's main purse into a fresh purse
+//! 2. Calls `start_recovery` on recovery_registry with that purse, which takes the bond
+//! 3. Returns whatever the registry did not take (more than the bond, or everything when
+//!    no bond was required) to the caller's main purse
+//!
+//! The bond is refunded when the recovery is finalized or expires, and forfeited
+//! if the owner cancels it or the guardians reject it.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use casper_contract::contract_api::{account, runtime, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::account::AccountHash;
+use casper_types::{Key, PublicKey, RuntimeArgs, U256, U512};
+use guardian_types::registry_contract_hash;
+use start_recovery_bonded::constants::{
+    REGISTRY_ARG_PURSE, REGISTRY_ENTRY_POINT_START_RECOVERY, RUNTIME_ARG_ACCOUNT,
+    RUNTIME_ARG_AMOUNT, RUNTIME_ARG_NEW_KEY, RUNTIME_ARG_NEW_KEYS, RUNTIME_ARG_REGISTRY,
+};
+use start_recovery_bonded::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let account: AccountHash = runtime::get_named_arg(RUNTIME_ARG_ACCOUNT);
+    let amount: U512 = runtime::get_named_arg(RUNTIME_ARG_AMOUNT);
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    // Step 1: Fund a purse holding the bond
+    let bond_purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), bond_purse, amount, None)
+        .unwrap_or_revert_with(UserError::BondTransferFailed);
+
+    // Step 2: Start the recovery, forwarding whichever key argument was given
+    let mut args = RuntimeArgs::new();
+    args.insert(RUNTIME_ARG_ACCOUNT, account).unwrap_or_revert();
+    match runtime::try_get_named_arg::<Vec<(PublicKey, u8)>>(RUNTIME_ARG_NEW_KEYS) {
+        Some(new_keys) => args.insert(RUNTIME_ARG_NEW_KEYS, new_keys),
+        None => args.insert(
+            RUNTIME_ARG_NEW_KEY,
+            runtime::get_named_arg::<PublicKey>(RUNTIME_ARG_NEW_KEY),
+        ),
+    }
+    .unwrap_or_revert();
+    args.insert(REGISTRY_ARG_PURSE, bond_purse).unwrap_or_revert();
+
+    runtime::call_contract::<U256>(registry_hash, REGISTRY_ENTRY_POINT_START_RECOVERY, args);
+
+    // Step 3: Return the remainder
+    let remainder = system::get_purse_balance(bond_purse).unwrap_or_revert();
+    if !remainder.is_zero() {
+        system::transfer_from_purse_to_purse(bond_purse, account::get_main_purse(), remainder, None)
+            .unwrap_or_revert_with(UserError::BondTransferFailed);
+    }
+}
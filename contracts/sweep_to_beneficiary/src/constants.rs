@@ -0,0 +1,6 @@
+pub const RUNTIME_ARG_REGISTRY: &str = "registry";
+pub const RUNTIME_ARG_RECOVERY_ID: &str = "recovery_id";
+pub const RUNTIME_ARG_RESERVE: &str = "reserve";
+
+pub const REGISTRY_ENTRY_POINT_GET_BENEFICIARY: &str = "get_beneficiary";
+pub const REGISTRY_ARG_ID: &str = "id";
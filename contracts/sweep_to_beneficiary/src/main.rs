@@ -0,0 +1,55 @@
+//! Beneficiary Sweep Contract
+//!
+//! Alternative to `recovery_key_rotation` for inheritance recoveries: instead of
+//! handing over the account, moves its funds to the heir.
+//! 1. Asks recovery_registry for the beneficiary of an approved inheritance recovery
+//! 2. Transfers the main purse balance, minus `reserve`, to that account
+//!
+//! The registry reverts the deploy unless the recovery is an approved, still open
+//! inheritance recovery of this account and the owner is still silent.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+use casper_contract::contract_api::{account, runtime, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::account::AccountHash;
+use casper_types::{runtime_args, Key, U256, U512};
+use guardian_types::registry_contract_hash;
+use sweep_to_beneficiary::constants::{
+    REGISTRY_ARG_ID, REGISTRY_ENTRY_POINT_GET_BENEFICIARY, RUNTIME_ARG_RECOVERY_ID,
+    RUNTIME_ARG_REGISTRY, RUNTIME_ARG_RESERVE,
+};
+use sweep_to_beneficiary::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // 1. Get runtime arguments
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let recovery_id: U256 = runtime::get_named_arg(RUNTIME_ARG_RECOVERY_ID);
+    let reserve: U512 = runtime::get_named_arg(RUNTIME_ARG_RESERVE);
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    // 2. The registry verifies the recovery and returns the beneficiary account
+    let beneficiary: AccountHash = runtime::call_contract(
+        registry_hash,
+        REGISTRY_ENTRY_POINT_GET_BENEFICIARY,
+        runtime_args! { REGISTRY_ARG_ID => recovery_id },
+    );
+
+    // 3. Sweep everything above the reserve
+    let main_purse = account::get_main_purse();
+    let balance =
+        system::get_purse_balance(main_purse).unwrap_or_revert_with(UserError::InvalidPurse);
+    let amount = match balance.checked_sub(reserve) {
+        Some(amount) if !amount.is_zero() => amount,
+        _ => runtime::revert(UserError::NothingToSweep),
+    };
+
+    system::transfer_from_purse_to_account(main_purse, beneficiary, amount, None)
+        .unwrap_or_revert_with(UserError::TransferFailed);
+}
@@ -0,0 +1,9 @@
+pub const RUNTIME_ARG_REGISTRY: &str = "registry";
+pub const RUNTIME_ARG_GUARDIAN_WEIGHT: &str = "guardian_weight";
+pub const RUNTIME_ARG_STALE_KEYS: &str = "stale_keys";
+
+/// Named key in the account holding the guardian set applied by setup or the last sync
+pub const NAMED_KEY_SYNCED_GUARDIANS: &str = guardian_types::SYNCED_GUARDIANS_KEY;
+
+pub const REGISTRY_ENTRY_POINT_GET_GUARDIANS: &str = "get_guardians";
+pub const REGISTRY_ARG_ACCOUNT: &str = "account";
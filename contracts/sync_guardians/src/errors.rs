@@ -0,0 +1,17 @@
+use casper_types::ApiError;
+
+#[repr(u16)]
+#[derive(Clone, Copy)]
+pub enum UserError {
+    InvalidRegistry,
+    AddGuardianFailed,
+    UpdateGuardianFailed,
+    RemoveGuardianFailed,
+    InvalidSyncedGuardians,
+}
+
+impl From<UserError> for ApiError {
+    fn from(error: UserError) -> Self {
+        ApiError::User(error as u16)
+    }
+}
@@ -0,0 +1,94 @@
+//! Guardian Sync Contract
+//!
+//! Makes the account's associated keys match its guardian list in recovery_registry:
+//! 1. Adds every registry guardian, or updates its weight if already associated
+//! 2. Removes guardians applied by a previous sync (or passed as `stale_keys`)
+//!    that are no longer in the registry
+//! 3. Records the applied guardian set in the account's named keys
+//!
+//! The account's own primary key is never added, updated or removed. Contract guardians
+//! cannot hold associated keys and are skipped.
+
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use casper_contract::contract_api::{account, runtime, storage};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::account::{AccountHash, AddKeyFailure, RemoveKeyFailure, Weight};
+use casper_types::{runtime_args, Key};
+use guardian_types::registry_contract_hash;
+use sync_guardians::constants::{
+    NAMED_KEY_SYNCED_GUARDIANS, REGISTRY_ARG_ACCOUNT, REGISTRY_ENTRY_POINT_GET_GUARDIANS,
+    RUNTIME_ARG_GUARDIAN_WEIGHT, RUNTIME_ARG_REGISTRY, RUNTIME_ARG_STALE_KEYS,
+};
+use sync_guardians::errors::UserError;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // 1. Get runtime arguments
+    let registry: Key = runtime::get_named_arg(RUNTIME_ARG_REGISTRY);
+    let guardian_weight: u8 = runtime::get_named_arg(RUNTIME_ARG_GUARDIAN_WEIGHT);
+    let stale_keys: Vec<AccountHash> =
+        runtime::try_get_named_arg(RUNTIME_ARG_STALE_KEYS).unwrap_or_default();
+
+    let registry_hash = registry_contract_hash(registry, UserError::InvalidRegistry);
+
+    let owner = runtime::get_caller();
+
+    // 2. Read the account guardians from the registry
+    let guardians: Vec<AccountHash> = runtime::call_contract::<Vec<Key>>(
+        registry_hash,
+        REGISTRY_ENTRY_POINT_GET_GUARDIANS,
+        runtime_args! { REGISTRY_ARG_ACCOUNT => owner },
+    )
+    .into_iter()
+    .filter_map(|guardian| guardian.into_account())
+    .collect();
+
+    // Step 1: Add or re-weight every registry guardian
+    for guardian in guardians.iter().filter(|guardian| **guardian != owner) {
+        match account::add_associated_key(*guardian, Weight::new(guardian_weight)) {
+            Ok(()) => {}
+            Err(AddKeyFailure::DuplicateKey) => {
+                account::update_associated_key(*guardian, Weight::new(guardian_weight))
+                    .unwrap_or_revert_with(UserError::UpdateGuardianFailed)
+            }
+            Err(_) => runtime::revert(UserError::AddGuardianFailed),
+        }
+    }
+
+    // Step 2: Remove guardians that are no longer in the registry
+    let synced_uref = runtime::get_key(NAMED_KEY_SYNCED_GUARDIANS).map(|key| {
+        key.into_uref()
+            .unwrap_or_revert_with(UserError::InvalidSyncedGuardians)
+    });
+    let mut previous: Vec<AccountHash> = match synced_uref {
+        Some(uref) => storage::read(uref)
+            .unwrap_or_revert_with(UserError::InvalidSyncedGuardians)
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    previous.extend(stale_keys);
+
+    for key in previous
+        .iter()
+        .filter(|key| **key != owner && !guardians.contains(key))
+    {
+        match account::remove_associated_key(*key) {
+            Ok(()) | Err(RemoveKeyFailure::MissingKey) => {}
+            Err(_) => runtime::revert(UserError::RemoveGuardianFailed),
+        }
+    }
+
+    // Step 3: Remember what was applied for the next sync
+    match synced_uref {
+        Some(uref) => storage::write(uref, guardians),
+        None => runtime::put_key(NAMED_KEY_SYNCED_GUARDIANS, storage::new_uref(guardians).into()),
+    }
+}
@@ -0,0 +1,201 @@
+//! Canonical message guardians sign to approve a recovery off-chain.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    Digest, HashAddr, PublicKey, U256,
+};
+
+/// Domain separator serialized at the start of every approval message
+pub const APPROVAL_MESSAGE_DOMAIN: &str = "sentinelx_approval";
+/// Prefix the Casper Wallet's `signMessage` puts in front of a message before signing it
+pub const WALLET_MESSAGE_PREFIX: &str = "Casper Message:\n";
+
+/// Approval of one recovery, bound to a chain, a registry deployment and the
+/// account's approval nonce so a signature cannot be replayed elsewhere.
+///
+/// Guardians sign [`ApprovalMessage::hash`], the blake2b-256 digest of the
+/// bytesrepr encoding (domain separator followed by the fields in order), in
+/// their wallet; the bytes actually signed are [`ApprovalMessage::signed_bytes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApprovalMessage {
+    /// Network name, e.g. `casper-test`
+    pub chain_name: String,
+    /// Contract hash of the recovery_registry deployment
+    pub registry: HashAddr,
+    /// Registry recovery id being approved
+    pub recovery_id: U256,
+    /// Account being recovered
+    pub account: AccountHash,
+    /// Every `(key, weight)` target the recovery rotates the account to, in order
+    pub targets: Vec<(PublicKey, u8)>,
+    /// Per-account approval nonce recorded when the recovery was started
+    pub nonce: u64,
+}
+
+impl ApprovalMessage {
+    /// Blake2b-256 digest of the serialized message; this is what guardians sign.
+    pub fn hash(&self) -> Result<[u8; Digest::LENGTH], bytesrepr::Error> {
+        Ok(Digest::hash(self.to_bytes()?).value())
+    }
+
+    /// Exact bytes the signature covers: [`WALLET_MESSAGE_PREFIX`] followed by the
+    /// lowercase hex of [`ApprovalMessage::hash`], which is what the wallet signs
+    /// when asked to `signMessage` that hex string.
+    pub fn signed_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        Ok(wallet_signed_bytes(&self.hash()?))
+    }
+}
+
+/// Bytes a wallet signs for `signMessage` of the lowercase hex of `digest`:
+/// [`WALLET_MESSAGE_PREFIX`] followed by that hex string.
+pub fn wallet_signed_bytes(digest: &[u8]) -> Vec<u8> {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut bytes = Vec::from(WALLET_MESSAGE_PREFIX.as_bytes());
+    for b in digest {
+        bytes.push(HEX[usize::from(b >> 4)]);
+        bytes.push(HEX[usize::from(b & 0x0f)]);
+    }
+    bytes
+}
+
+impl ToBytes for ApprovalMessage {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(APPROVAL_MESSAGE_DOMAIN.to_bytes()?);
+        buffer.extend(self.chain_name.to_bytes()?);
+        buffer.extend(self.registry.to_bytes()?);
+        buffer.extend(self.recovery_id.to_bytes()?);
+        buffer.extend(self.account.to_bytes()?);
+        buffer.extend(self.targets.to_bytes()?);
+        buffer.extend(self.nonce.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        APPROVAL_MESSAGE_DOMAIN.serialized_length()
+            + self.chain_name.serialized_length()
+            + self.registry.serialized_length()
+            + self.recovery_id.serialized_length()
+            + self.account.serialized_length()
+            + self.targets.serialized_length()
+            + self.nonce.serialized_length()
+    }
+}
+
+impl FromBytes for ApprovalMessage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (domain, rem) = String::from_bytes(bytes)?;
+        if domain != APPROVAL_MESSAGE_DOMAIN {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (chain_name, rem) = FromBytes::from_bytes(rem)?;
+        let (registry, rem) = FromBytes::from_bytes(rem)?;
+        let (recovery_id, rem) = FromBytes::from_bytes(rem)?;
+        let (account, rem) = FromBytes::from_bytes(rem)?;
+        let (targets, rem) = FromBytes::from_bytes(rem)?;
+        let (nonce, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            ApprovalMessage {
+                chain_name,
+                registry,
+                recovery_id,
+                account,
+                targets,
+                nonce,
+            },
+            rem,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use casper_types::{crypto, SecretKey};
+    use core::fmt::Write;
+
+    fn message() -> ApprovalMessage {
+        let key = |seed| PublicKey::from(&SecretKey::ed25519_from_bytes([seed; 32]).unwrap());
+        ApprovalMessage {
+            chain_name: String::from("casper-test"),
+            registry: [0x11; 32],
+            recovery_id: U256::from(7),
+            account: AccountHash::new([0x22; 32]),
+            targets: vec![(key(1), 3), (key(2), 1)],
+            nonce: 4,
+        }
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut out, b| {
+            let _ = write!(out, "{:02x}", b);
+            out
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let msg = message();
+        let bytes = msg.to_bytes().unwrap();
+        assert_eq!(bytes.len(), msg.serialized_length());
+        let (decoded, rem) = ApprovalMessage::from_bytes(&bytes).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn rejects_other_domains() {
+        let mut bytes = message().to_bytes().unwrap();
+        // First byte after the u32 length prefix of the domain separator
+        bytes[4] ^= 1;
+        assert!(ApprovalMessage::from_bytes(&bytes).is_err());
+    }
+
+    /// blake2b-256 of: u32-length-prefixed "sentinelx_approval" and "casper-test", 32 x 0x11,
+    /// U256 7 (`01 07`), 32 x 0x22, u32 target count 2, each tagged Ed25519 key of secret
+    /// [seed; 32] followed by its weight, and u64 nonce 4, all little endian
+    #[test]
+    fn fixed_vector() {
+        assert_eq!(
+            hex(&message().hash().unwrap()),
+            "8ce9d94054bf28d150dc7cd4abc800ce26a205a04e7eaaffb80fca8bb3ecaa9a"
+        );
+    }
+
+    #[test]
+    fn every_target_is_signed() {
+        let mut other = message();
+        other.targets[1].1 = 2;
+        assert_ne!(other.hash().unwrap(), message().hash().unwrap());
+        other.targets.pop();
+        assert_ne!(other.hash().unwrap(), message().hash().unwrap());
+    }
+
+    #[test]
+    fn wallet_signed_bytes() {
+        assert_eq!(
+            message().signed_bytes().unwrap(),
+            b"Casper Message:\n8ce9d94054bf28d150dc7cd4abc800ce26a205a04e7eaaffb80fca8bb3ecaa9a"
+        );
+    }
+
+    #[test]
+    fn wallet_signatures_verify() {
+        let bytes = message().signed_bytes().unwrap();
+        for secret in [
+            SecretKey::ed25519_from_bytes([5; 32]).unwrap(),
+            SecretKey::secp256k1_from_bytes([5; 32]).unwrap(),
+        ] {
+            let public = PublicKey::from(&secret);
+            let signature = crypto::sign(&bytes, &secret, &public);
+            assert!(crypto::verify(&bytes, &signature, &public).is_ok());
+            // A signature over the bare digest is not accepted
+            let bare = crypto::sign(message().hash().unwrap(), &secret, &public);
+            assert!(crypto::verify(&bytes, &bare, &public).is_err());
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Runtime argument decoding shared by the session WASMs.
+
+use alloc::vec::Vec;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes},
+    Key, PublicKey,
+};
+
+/// `<name>_kind` value of an account argument passed as a `Key` (the default)
+pub const ACCOUNT_ARG_KEY: u8 = 0;
+/// `<name>_kind` value of an account argument passed as a `PublicKey`
+pub const ACCOUNT_ARG_PUBLIC_KEY: u8 = 1;
+/// `<name>_kind` value of an account argument passed as a `ByteArray(32)` account hash
+pub const ACCOUNT_ARG_ACCOUNT_HASH: u8 = 2;
+/// Suffix of the argument naming how an account argument is encoded
+pub const ACCOUNT_ARG_KIND_SUFFIX: &str = "_kind";
+
+/// An account argument passed as a `PublicKey`, a `ByteArray(32)` account hash
+/// or a `Key`.
+///
+/// The host only hands session code the serialized value of a named argument,
+/// and a `Key::Hash` has the exact encoding of an Ed25519 public key, so the
+/// encoding is never guessed: the caller names it with a `<name>_kind` U8
+/// argument ([`ACCOUNT_ARG_KEY`] when absent) and the value must decode
+/// as exactly that type.
+///
+/// Non-account keys decode to [`AccountArg::Key`] and have no
+/// [`account_hash`](AccountArg::account_hash), so callers can reject them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountArg {
+    PublicKey(PublicKey),
+    AccountHash(AccountHash),
+    Key(Key),
+}
+
+impl AccountArg {
+    /// Decode the serialized value of an account argument of the given `kind`, failing on an
+    /// unknown kind or bytes that are not exactly a value of that type.
+    pub fn decode(kind: u8, bytes: &[u8]) -> Result<AccountArg, bytesrepr::Error> {
+        match kind {
+            ACCOUNT_ARG_KEY => bytesrepr::deserialize_from_slice(bytes).map(AccountArg::Key),
+            ACCOUNT_ARG_PUBLIC_KEY => bytesrepr::deserialize_from_slice(bytes).map(AccountArg::PublicKey),
+            ACCOUNT_ARG_ACCOUNT_HASH => bytesrepr::deserialize_from_slice(bytes).map(AccountArg::AccountHash),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+
+    /// Account hash this argument refers to, or `None` for a non-account `Key`.
+    pub fn account_hash(&self) -> Option<AccountHash> {
+        match self {
+            AccountArg::PublicKey(public_key) => Some(AccountHash::from(public_key)),
+            AccountArg::AccountHash(account_hash) => Some(*account_hash),
+            AccountArg::Key(key) => key.into_account(),
+        }
+    }
+}
+
+/// The raw serialized value of a named argument, whatever its `CLType`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgBytes(pub Vec<u8>);
+
+impl FromBytes for ArgBytes {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Ok((ArgBytes(bytes.into()), &[]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{bytesrepr::ToBytes, AccessRights, SecretKey, URef};
+
+    fn decode(kind: u8, value: impl ToBytes) -> Result<AccountArg, bytesrepr::Error> {
+        AccountArg::decode(kind, &value.to_bytes().unwrap())
+    }
+
+    fn ed25519() -> PublicKey {
+        PublicKey::from(&SecretKey::ed25519_from_bytes([7; 32]).unwrap())
+    }
+
+    fn secp256k1() -> PublicKey {
+        PublicKey::from(&SecretKey::secp256k1_from_bytes([7; 32]).unwrap())
+    }
+
+    #[test]
+    fn account_hash_bytes() {
+        let hash = AccountHash::new([9; 32]);
+        let arg = decode(ACCOUNT_ARG_ACCOUNT_HASH, hash).unwrap();
+        assert_eq!(arg, AccountArg::AccountHash(hash));
+        assert_eq!(arg.account_hash(), Some(hash));
+    }
+
+    #[test]
+    fn public_keys() {
+        for key in [ed25519(), secp256k1()] {
+            let arg = decode(ACCOUNT_ARG_PUBLIC_KEY, key.clone()).unwrap();
+            assert_eq!(arg, AccountArg::PublicKey(key.clone()));
+            assert_eq!(arg.account_hash(), Some(key.to_account_hash()));
+        }
+    }
+
+    #[test]
+    fn account_key() {
+        let hash = AccountHash::new([9; 32]);
+        let arg = decode(ACCOUNT_ARG_KEY, Key::Account(hash)).unwrap();
+        assert_eq!(arg, AccountArg::Key(Key::Account(hash)));
+        assert_eq!(arg.account_hash(), Some(hash));
+    }
+
+    #[test]
+    fn non_account_keys_are_rejected() {
+        let uref = Key::URef(URef::new([7; 32], AccessRights::READ_ADD_WRITE));
+        for key in [Key::Hash([2; 32]), uref, Key::Dictionary([3; 32])] {
+            assert_eq!(decode(ACCOUNT_ARG_KEY, key).unwrap().account_hash(), None);
+        }
+    }
+
+    /// [1; 32] is a valid Ed25519 point, so this `Key::Hash` also parses as a public key: passed
+    /// as a key it is rejected rather than read as an unrelated account
+    #[test]
+    fn hash_key_on_the_curve_is_rejected() {
+        let key = Key::Hash([1; 32]);
+        assert!(PublicKey::from_bytes(&key.to_bytes().unwrap()).is_ok());
+        assert_eq!(decode(ACCOUNT_ARG_KEY, key).unwrap().account_hash(), None);
+    }
+
+    #[test]
+    fn value_must_match_its_kind() {
+        let hash = AccountHash::new([9; 32]);
+        assert!(decode(ACCOUNT_ARG_PUBLIC_KEY, hash).is_err());
+        assert!(decode(ACCOUNT_ARG_ACCOUNT_HASH, ed25519()).is_err());
+        assert!(decode(ACCOUNT_ARG_KEY, secp256k1()).is_err());
+        assert!(decode(ACCOUNT_ARG_ACCOUNT_HASH, Key::Account(hash)).is_err());
+    }
+
+    #[test]
+    fn unknown_kind_fails() {
+        assert!(decode(3, ed25519()).is_err());
+        assert!(decode(u8::MAX, AccountHash::new([9; 32])).is_err());
+    }
+
+    #[test]
+    fn arg_bytes_takes_the_whole_value() {
+        let (ArgBytes(bytes), rem) = ArgBytes::from_bytes(&[1, 2, 3]).unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
+        assert!(rem.is_empty());
+    }
+
+    #[test]
+    fn malformed_bytes_fail() {
+        for kind in [ACCOUNT_ARG_PUBLIC_KEY, ACCOUNT_ARG_ACCOUNT_HASH, ACCOUNT_ARG_KEY] {
+            assert!(AccountArg::decode(kind, &[]).is_err());
+            assert!(AccountArg::decode(kind, &[0xff; 20]).is_err());
+        }
+    }
+}
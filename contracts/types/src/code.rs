@@ -0,0 +1,73 @@
+//! Recovery codes: printable backup codes that act as guardians.
+//!
+//! A code is the seed of an Ed25519 key. The owner registers the account hash
+//! of that key and the code is used by signing the recovery's
+//! [`ApprovalMessage`](crate::ApprovalMessage) with it, so a signature seen in
+//! a pending deploy can only ever approve the recovery it was made for.
+
+use alloc::vec::Vec;
+
+use casper_types::{account::AccountHash, crypto, Digest, PublicKey, SecretKey};
+
+/// Domain separator hashed in front of a code to derive its key
+pub const RECOVERY_CODE_DOMAIN: &str = "sentinelx_recovery_code";
+
+/// Ed25519 secret key of a printable recovery code: the blake2b-256 hash of
+/// the domain separator followed by the UTF-8 code.
+pub fn recovery_code_secret_key(code: &str) -> Result<SecretKey, crypto::Error> {
+    let mut seed = Vec::from(RECOVERY_CODE_DOMAIN.as_bytes());
+    seed.extend_from_slice(code.as_bytes());
+    SecretKey::ed25519_from_bytes(Digest::hash(seed).value())
+}
+
+/// Public key of a recovery code, passed to `approve_with_code` with its signature.
+pub fn recovery_code_key(code: &str) -> Result<PublicKey, crypto::Error> {
+    Ok(PublicKey::from(&recovery_code_secret_key(code)?))
+}
+
+/// What the owner registers in `recovery_codes`: the account hash of the code's key.
+pub fn recovery_code_hash(code: &str) -> Result<AccountHash, crypto::Error> {
+    Ok(recovery_code_key(code)?.to_account_hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApprovalMessage;
+    use alloc::{string::String, vec};
+    use casper_types::U256;
+
+    /// Ed25519 key seeded with blake2b-256("sentinelx_recovery_codeK7QM-2XWD-94HT"), hashed
+    /// into an account hash (blake2b-256 of "ed25519", a zero byte and the public key)
+    #[test]
+    fn fixed_vector() {
+        assert_eq!(
+            recovery_code_hash("K7QM-2XWD-94HT").unwrap().to_formatted_string(),
+            "account-hash-fe8691aebf9cb63bb214aded0b3da6304f4819b1ceb87d544b2a14ed6f4a4b0b"
+        );
+    }
+
+    #[test]
+    fn codes_have_distinct_keys() {
+        assert_ne!(recovery_code_hash("K7QM-2XWD-94HT").unwrap(), recovery_code_hash("K7QM-2XWD-94HU").unwrap());
+    }
+
+    #[test]
+    fn signature_is_bound_to_the_recovery() {
+        let secret = recovery_code_secret_key("K7QM-2XWD-94HT").unwrap();
+        let public = recovery_code_key("K7QM-2XWD-94HT").unwrap();
+        let mut msg = ApprovalMessage {
+            chain_name: String::from("casper-test"),
+            registry: [0x11; 32],
+            recovery_id: U256::from(7),
+            account: AccountHash::new([0x22; 32]),
+            targets: vec![(public.clone(), 1)],
+            nonce: 4,
+        };
+        let signature = crypto::sign(msg.signed_bytes().unwrap(), &secret, &public);
+        assert!(crypto::verify(msg.signed_bytes().unwrap(), &signature, &public).is_ok());
+
+        msg.recovery_id = U256::from(8);
+        assert!(crypto::verify(msg.signed_bytes().unwrap(), &signature, &public).is_err());
+    }
+}
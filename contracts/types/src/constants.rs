@@ -3,6 +3,10 @@
 /// Minimum required guardians for an account
 pub const MIN_GUARDIANS: usize = 2;
 
+/// Named key in a protected account holding the guardian keys applied by
+/// `setup_guardians` or the last `sync_guardians`
+pub const SYNCED_GUARDIANS_KEY: &str = "sentinelx_synced_guardians";
+
 /// Storage key prefixes
 pub mod storage_keys {
     /// Prefix for guardian list storage
@@ -1,14 +1,35 @@
 //! SentinelX - Shared Types (Simplified)
 //!
-//! Minimal shared types for the recovery_registry contract.
-//! Session WASMs don't need this library - they're self-contained.
+//! Minimal shared types for the recovery_registry contract, the
+//! runtime argument decoding used by the session WASMs, the recovery
+//! receipts they leave in recovered accounts, the approval message
+//! guardians sign off-chain, the rollback message a soft-retired key
+//! signs, the keys behind recovery codes and the
+//! guardian policy checks. With the `contract` feature it also holds
+//! the helpers the session WASMs share.
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(test), no_std)]
 
 extern crate alloc;
 
+pub mod approval;
+pub mod args;
+pub mod code;
 pub mod constants;
 pub mod errors;
+pub mod policy;
+pub mod receipt;
+pub mod rollback;
+#[cfg(feature = "contract")]
+pub mod session;
 
+pub use approval::*;
+pub use args::*;
+pub use code::*;
 pub use constants::*;
 pub use errors::*;
+pub use policy::*;
+pub use receipt::*;
+pub use rollback::*;
+#[cfg(feature = "contract")]
+pub use session::*;
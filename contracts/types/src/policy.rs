@@ -0,0 +1,261 @@
+//! Guardian approval, threshold and freeze policy checks shared by the registry, the session
+//! WASMs and off-chain tooling.
+
+use alloc::vec::Vec;
+
+use casper_types::{Key, U256};
+
+/// A guardian group: its members and how many of them must approve
+pub type GuardianGroup = (Vec<Key>, u8);
+
+/// What the registry records about a recovery proposal's approval
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProposalApproval {
+    /// Closed without being finalized: cancelled, rejected, expired or beaten by a competing proposal
+    pub closed: bool,
+    /// A drill started with `start_drill`
+    pub drill: bool,
+    /// A guardian (or beneficiary) quorum approved it
+    pub quorum: bool,
+    /// When the backup key's approval takes effect, if it approved
+    pub backup_ready_at: Option<u64>,
+}
+
+impl ProposalApproval {
+    /// Approved by a quorum, or by the backup key once its delay has passed. Drills never are,
+    /// and neither is a proposal closed without being finalized.
+    pub fn approved(&self, now: u64) -> bool {
+        !self.drill && !self.closed && (self.quorum || self.backup_ready_at.is_some_and(|ready| now >= ready))
+    }
+}
+
+/// Per-action thresholds in the order recovery approval, rejection, guardian-set change and freeze
+pub type ActionThresholds = [u8; 4];
+
+/// Whether `thresholds` can be met by `total` guardians and recovery codes: none is 0 or exceeds
+/// `total`, and changing the guardians takes at least as many as a recovery, so a stolen owner key
+/// cannot swap in guardians more cheaply than it could recover the account.
+pub fn thresholds_valid(thresholds: &ActionThresholds, total: usize) -> bool {
+    let [approval, rejection, change, freeze] = *thresholds;
+    [approval, rejection, change, freeze].iter().all(|t| *t != 0 && *t as usize <= total)
+        && change >= approval
+}
+
+/// Whether `groups` with `outer` of them required is a valid policy over `guardians`: every
+/// group has a reachable non-zero threshold, every member is a guardian belonging to exactly
+/// one group, and `outer` is between 1 and the number of groups.
+pub fn groups_valid(groups: &[GuardianGroup], outer: u8, guardians: &[Key]) -> bool {
+    let mut seen: Vec<&Key> = Vec::new();
+    for (members, threshold) in groups {
+        if *threshold == 0 || *threshold as usize > members.len() {
+            return false;
+        }
+        for member in members {
+            if !guardians.contains(member) || seen.contains(&member) {
+                return false;
+            }
+            seen.push(member);
+        }
+    }
+    outer != 0 && outer as usize <= groups.len()
+}
+
+/// Whether at least `outer` groups have at least their threshold of members for which
+/// `approved` holds.
+pub fn groups_satisfied(
+    groups: &[GuardianGroup],
+    outer: u8,
+    approved: impl Fn(&Key) -> bool,
+) -> bool {
+    let satisfied = groups
+        .iter()
+        .filter(|(members, threshold)| {
+            members.iter().filter(|m| approved(m)).count() >= *threshold as usize
+        })
+        .count();
+    satisfied >= outer as usize
+}
+
+/// Whether freezing to the `new` (deployment, key management) thresholds locks out an owner key
+/// of `weight` on an account with the `current` thresholds: both must exceed the key's weight,
+/// so it can neither deploy nor manage keys alone, and the current thresholds.
+pub fn freeze_thresholds_valid(weight: u8, current: (u8, u8), new: (u8, u8)) -> bool {
+    new.0 > weight && new.1 > weight && new.0 > current.0 && new.1 > current.1
+}
+
+/// Proposals whose votes a migrating guardian carries over, once each: the open proposals of
+/// every account they protect or were nominated by. The guardian's own recovery index is not
+/// enough, since it misses proposals started before they accepted.
+pub fn migrated_proposals(open_per_account: impl IntoIterator<Item = Vec<U256>>) -> Vec<U256> {
+    let mut ids: Vec<U256> = Vec::new();
+    for id in open_per_account.into_iter().flatten() {
+        if !ids.contains(&id) { ids.push(id); }
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn guard(seed: u8) -> Key {
+        Key::Hash([seed; 32])
+    }
+
+    /// "one of family AND two of three colleagues"
+    fn policy() -> (Vec<GuardianGroup>, u8) {
+        (vec![(vec![guard(1), guard(2)], 1), (vec![guard(3), guard(4), guard(5)], 2)], 2)
+    }
+
+    fn guardians() -> Vec<Key> {
+        (1..=5).map(guard).collect()
+    }
+
+    #[test]
+    fn backup_approval_counts_after_its_delay() {
+        let proposal = ProposalApproval { backup_ready_at: Some(100), ..Default::default() };
+        assert!(!proposal.approved(99));
+        assert!(proposal.approved(100));
+        assert!(ProposalApproval { quorum: true, ..Default::default() }.approved(0));
+    }
+
+    /// `is_approved` and `record_rotation` both go through `approved`: a proposal cancelled after
+    /// the backup key approved it must stay unapproved once the delay has passed
+    #[test]
+    fn cancelled_backup_approval_never_counts() {
+        let mut proposal = ProposalApproval { backup_ready_at: Some(100), ..Default::default() };
+        proposal.closed = true;
+        assert!(!proposal.approved(100));
+        assert!(!proposal.approved(u64::MAX));
+        proposal.quorum = true;
+        assert!(!proposal.approved(u64::MAX));
+    }
+
+    #[test]
+    fn drills_are_never_approved() {
+        let proposal = ProposalApproval { drill: true, quorum: true, backup_ready_at: Some(0), ..Default::default() };
+        assert!(!proposal.approved(u64::MAX));
+    }
+
+    #[test]
+    fn valid_thresholds() {
+        assert!(thresholds_valid(&[2, 2, 2, 2], 3));
+        assert!(thresholds_valid(&[2, 1, 3, 2], 3));
+    }
+
+    #[test]
+    fn rejects_zero_thresholds() {
+        assert!(!thresholds_valid(&[0, 2, 2, 2], 3));
+        assert!(!thresholds_valid(&[2, 0, 2, 2], 3));
+        assert!(!thresholds_valid(&[2, 2, 2, 0], 3));
+        assert!(!thresholds_valid(&[3, 1, 0, 2], 3));
+    }
+
+    #[test]
+    fn guardian_change_needs_a_recovery_quorum() {
+        assert!(thresholds_valid(&[2, 2, 2, 2], 3));
+        assert!(!thresholds_valid(&[3, 2, 2, 2], 3));
+        assert!(!thresholds_valid(&[2, 2, 1, 2], 3));
+    }
+
+    #[test]
+    fn rejects_unreachable_thresholds() {
+        assert!(!thresholds_valid(&[4, 2, 2, 2], 3));
+        assert!(!thresholds_valid(&[2, 4, 2, 2], 3));
+        assert!(!thresholds_valid(&[2, 2, 4, 2], 3));
+        assert!(!thresholds_valid(&[2, 2, 2, 4], 3));
+        assert!(!thresholds_valid(&[1, 1, 0, 1], 0));
+    }
+
+    #[test]
+    fn freeze_locks_out_the_owner_key() {
+        assert!(freeze_thresholds_valid(3, (1, 2), (4, 4)));
+        assert!(freeze_thresholds_valid(1, (1, 1), (2, 2)));
+    }
+
+    /// Above the current thresholds but not above the stolen key's weight: the key could still deploy
+    #[test]
+    fn freeze_must_exceed_the_key_weight() {
+        assert!(!freeze_thresholds_valid(3, (1, 1), (2, 2)));
+        assert!(!freeze_thresholds_valid(3, (1, 1), (3, 3)));
+        assert!(!freeze_thresholds_valid(3, (1, 1), (4, 3)));
+    }
+
+    #[test]
+    fn freeze_must_raise_the_thresholds() {
+        assert!(!freeze_thresholds_valid(1, (5, 5), (4, 6)));
+        assert!(!freeze_thresholds_valid(1, (5, 5), (6, 5)));
+    }
+
+    /// A guardian who accepted after proposal 7 started has no index entry for it, but still
+    /// voted on it through the account
+    #[test]
+    fn migration_covers_every_open_proposal_of_the_accounts() {
+        let index = [U256::from(9)];
+        let accounts = vec![vec![U256::from(7), U256::from(9)], vec![], vec![U256::from(9), U256::from(11)]];
+        let ids = migrated_proposals(accounts);
+        assert_eq!(ids, vec![U256::from(7), U256::from(9), U256::from(11)]);
+        assert!(index.iter().all(|id| ids.contains(id)));
+    }
+
+    #[test]
+    fn valid_policy() {
+        let (groups, outer) = policy();
+        assert!(groups_valid(&groups, outer, &guardians()));
+    }
+
+    #[test]
+    fn rejects_bad_thresholds() {
+        let (mut groups, outer) = policy();
+        assert!(!groups_valid(&groups, 0, &guardians()));
+        assert!(!groups_valid(&groups, 3, &guardians()));
+        groups[0].1 = 0;
+        assert!(!groups_valid(&groups, outer, &guardians()));
+        groups[0].1 = 3;
+        assert!(!groups_valid(&groups, outer, &guardians()));
+    }
+
+    #[test]
+    fn rejects_non_guardians() {
+        let (mut groups, outer) = policy();
+        groups[0].0.push(guard(6));
+        assert!(!groups_valid(&groups, outer, &guardians()));
+    }
+
+    #[test]
+    fn rejects_duplicate_members() {
+        let (mut groups, outer) = policy();
+        groups[0].0.push(guard(1));
+        assert!(!groups_valid(&groups, outer, &guardians()));
+
+        let (mut groups, outer) = policy();
+        groups[1].0.push(guard(2));
+        assert!(!groups_valid(&groups, outer, &guardians()));
+    }
+
+    /// What `decline_guardianship` checks: dropping a member can leave a group unreachable
+    #[test]
+    fn removing_members_can_break_groups() {
+        let (mut groups, outer) = policy();
+        let mut guardians = guardians();
+        guardians.retain(|g| *g != guard(3));
+        groups[1].0.retain(|m| *m != guard(3));
+        assert!(groups_valid(&groups, outer, &guardians));
+
+        guardians.retain(|g| *g != guard(4));
+        groups[1].0.retain(|m| *m != guard(4));
+        assert!(!groups_valid(&groups, outer, &guardians));
+    }
+
+    #[test]
+    fn satisfied_needs_enough_groups() {
+        let (groups, outer) = policy();
+        let approved = |set: &'static [u8]| move |k: &Key| set.iter().any(|s| *k == guard(*s));
+        assert!(groups_satisfied(&groups, outer, approved(&[2, 3, 5])));
+        // Three approvals, but the family group has none
+        assert!(!groups_satisfied(&groups, outer, approved(&[3, 4, 5])));
+        assert!(!groups_satisfied(&groups, outer, approved(&[1, 2, 3])));
+        assert!(groups_satisfied(&groups, 1, approved(&[1])));
+    }
+}
@@ -0,0 +1,203 @@
+//! Recovery receipts written into a recovered account by the rotation WASM.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, U256,
+};
+
+/// Named key in the recovered account holding its `Vec<RecoveryReceipt>` history
+pub const RECOVERY_RECEIPTS_KEY: &str = "sentinelx_recovery_receipts";
+
+/// Record of one key rotation performed by `recovery_key_rotation`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoveryReceipt {
+    /// Registry recovery id, if the rotation was given one
+    pub recovery_id: Option<U256>,
+    /// Key that was removed or soft-retired
+    pub old_key: AccountHash,
+    /// Keys added by the rotation with their weights
+    pub new_keys: Vec<(AccountHash, u8)>,
+    /// (deployment, key management) thresholds before the rotation, if supplied
+    pub previous_thresholds: Option<(u8, u8)>,
+    /// (deployment, key management) thresholds set by the rotation
+    pub new_thresholds: (u8, u8),
+    /// Whether the old key was kept at weight 0 instead of being removed
+    pub soft_retired: bool,
+    /// Block time of the rotation in milliseconds
+    pub block_time: u64,
+}
+
+/// The plain tuple a receipt serializes as, so node tooling can parse stored receipts without
+/// this crate: `(recovery_id, old_key, (new_keys, previous_thresholds, (new_thresholds,
+/// soft_retired, block_time)))`, nesting because CLType tuples hold at most three fields.
+pub type RecoveryReceiptTuple = (
+    Option<U256>,
+    AccountHash,
+    (Vec<(AccountHash, u8)>, Option<(u8, u8)>, ((u8, u8), bool, u64)),
+);
+
+impl CLTyped for RecoveryReceipt {
+    fn cl_type() -> CLType {
+        RecoveryReceiptTuple::cl_type()
+    }
+}
+
+impl ToBytes for RecoveryReceipt {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.recovery_id.to_bytes()?);
+        buffer.extend(self.old_key.to_bytes()?);
+        buffer.extend(self.new_keys.to_bytes()?);
+        buffer.extend(self.previous_thresholds.to_bytes()?);
+        buffer.extend(self.new_thresholds.to_bytes()?);
+        buffer.extend(self.soft_retired.to_bytes()?);
+        buffer.extend(self.block_time.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.recovery_id.serialized_length()
+            + self.old_key.serialized_length()
+            + self.new_keys.serialized_length()
+            + self.previous_thresholds.serialized_length()
+            + self.new_thresholds.serialized_length()
+            + self.soft_retired.serialized_length()
+            + self.block_time.serialized_length()
+    }
+}
+
+impl FromBytes for RecoveryReceipt {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (recovery_id, rem) = FromBytes::from_bytes(bytes)?;
+        let (old_key, rem) = FromBytes::from_bytes(rem)?;
+        let (new_keys, rem) = FromBytes::from_bytes(rem)?;
+        let (previous_thresholds, rem) = FromBytes::from_bytes(rem)?;
+        let (new_thresholds, rem) = FromBytes::from_bytes(rem)?;
+        let (soft_retired, rem) = FromBytes::from_bytes(rem)?;
+        let (block_time, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            RecoveryReceipt {
+                recovery_id,
+                old_key,
+                new_keys,
+                previous_thresholds,
+                new_thresholds,
+                soft_retired,
+                block_time,
+            },
+            rem,
+        ))
+    }
+}
+
+impl Display for RecoveryReceipt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.recovery_id {
+            Some(id) => write!(f, "recovery #{}", id)?,
+            None => write!(f, "recovery (no id)")?,
+        }
+        write!(f, " at {} ms: old key {}", self.block_time, self.old_key)?;
+        if self.soft_retired {
+            write!(f, " (soft-retired)")?;
+        }
+        write!(f, ", new keys [")?;
+        for (i, (key, weight)) in self.new_keys.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} (weight {})", key, weight)?;
+        }
+        write!(f, "], thresholds ")?;
+        match self.previous_thresholds {
+            Some((deployment, key_management)) => {
+                write!(f, "{}/{}", deployment, key_management)?
+            }
+            None => write!(f, "?/?")?,
+        }
+        write!(
+            f,
+            " -> {}/{} (deployment/key management)",
+            self.new_thresholds.0, self.new_thresholds.1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+
+    fn receipt() -> RecoveryReceipt {
+        RecoveryReceipt {
+            recovery_id: Some(U256::from(7)),
+            old_key: AccountHash::new([1; 32]),
+            new_keys: vec![(AccountHash::new([2; 32]), 3), (AccountHash::new([3; 32]), 1)],
+            previous_thresholds: Some((1, 2)),
+            new_thresholds: (3, 3),
+            soft_retired: true,
+            block_time: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for receipt in [
+            receipt(),
+            RecoveryReceipt {
+                recovery_id: None,
+                previous_thresholds: None,
+                soft_retired: false,
+                ..receipt()
+            },
+        ] {
+            let bytes = receipt.to_bytes().unwrap();
+            assert_eq!(bytes.len(), receipt.serialized_length());
+            let (decoded, rem) = RecoveryReceipt::from_bytes(&bytes).unwrap();
+            assert!(rem.is_empty());
+            assert_eq!(decoded, receipt);
+        }
+    }
+
+    #[test]
+    fn encoded_as_typed_tuple() {
+        let r = receipt();
+        let tuple: RecoveryReceiptTuple = (
+            r.recovery_id,
+            r.old_key,
+            (
+                r.new_keys.clone(),
+                r.previous_thresholds,
+                (r.new_thresholds, r.soft_retired, r.block_time),
+            ),
+        );
+        assert_eq!(RecoveryReceipt::cl_type(), RecoveryReceiptTuple::cl_type());
+        assert_eq!(r.to_bytes().unwrap(), tuple.to_bytes().unwrap());
+    }
+
+    #[test]
+    fn truncated_bytes_fail() {
+        let bytes = receipt().to_bytes().unwrap();
+        assert!(RecoveryReceipt::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let text = receipt().to_string();
+        assert!(text.starts_with("recovery #7 at 1700000000000 ms: old key "));
+        assert!(text.contains("(soft-retired), new keys ["));
+        assert!(text.ends_with("thresholds 1/2 -> 3/3 (deployment/key management)"));
+
+        let text = RecoveryReceipt {
+            recovery_id: None,
+            previous_thresholds: None,
+            ..receipt()
+        }
+        .to_string();
+        assert!(text.starts_with("recovery (no id)"));
+        assert!(text.contains("thresholds ?/? -> 3/3"));
+    }
+}
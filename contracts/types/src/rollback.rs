@@ -0,0 +1,141 @@
+//! Message the holder of a soft-retired key signs to authorize a rollback.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    Digest, HashAddr, U256,
+};
+
+use crate::wallet_signed_bytes;
+
+/// Domain separator serialized at the start of every rollback message
+pub const ROLLBACK_MESSAGE_DOMAIN: &str = "sentinelx_rollback";
+
+/// Authorization to roll back one soft-retirement recovery, bound to a chain
+/// and a registry deployment. A recovery is rolled back at most once, so the
+/// recovery id is enough to keep the signature from being replayed.
+///
+/// The old key signs [`RollbackMessage::hash`] in its wallet like guardians
+/// sign an [`ApprovalMessage`](crate::ApprovalMessage); the bytes actually
+/// signed are [`RollbackMessage::signed_bytes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RollbackMessage {
+    /// Network name, e.g. `casper-test`
+    pub chain_name: String,
+    /// Contract hash of the recovery_registry deployment
+    pub registry: HashAddr,
+    /// Registry recovery id being rolled back
+    pub recovery_id: U256,
+    /// Account the recovery rotated
+    pub account: AccountHash,
+}
+
+impl RollbackMessage {
+    /// Blake2b-256 digest of the serialized message
+    pub fn hash(&self) -> Result<[u8; Digest::LENGTH], bytesrepr::Error> {
+        Ok(Digest::hash(self.to_bytes()?).value())
+    }
+
+    /// Exact bytes the old key's signature covers (see [`wallet_signed_bytes`])
+    pub fn signed_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        Ok(wallet_signed_bytes(&self.hash()?))
+    }
+}
+
+impl ToBytes for RollbackMessage {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(ROLLBACK_MESSAGE_DOMAIN.to_bytes()?);
+        buffer.extend(self.chain_name.to_bytes()?);
+        buffer.extend(self.registry.to_bytes()?);
+        buffer.extend(self.recovery_id.to_bytes()?);
+        buffer.extend(self.account.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        ROLLBACK_MESSAGE_DOMAIN.serialized_length()
+            + self.chain_name.serialized_length()
+            + self.registry.serialized_length()
+            + self.recovery_id.serialized_length()
+            + self.account.serialized_length()
+    }
+}
+
+impl FromBytes for RollbackMessage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (domain, rem) = String::from_bytes(bytes)?;
+        if domain != ROLLBACK_MESSAGE_DOMAIN {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (chain_name, rem) = FromBytes::from_bytes(rem)?;
+        let (registry, rem) = FromBytes::from_bytes(rem)?;
+        let (recovery_id, rem) = FromBytes::from_bytes(rem)?;
+        let (account, rem) = FromBytes::from_bytes(rem)?;
+        Ok((
+            RollbackMessage {
+                chain_name,
+                registry,
+                recovery_id,
+                account,
+            },
+            rem,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApprovalMessage;
+    use alloc::vec;
+    use casper_types::{crypto, PublicKey, SecretKey};
+
+    fn message() -> RollbackMessage {
+        RollbackMessage {
+            chain_name: String::from("casper-test"),
+            registry: [0x11; 32],
+            recovery_id: U256::from(7),
+            account: AccountHash::new([0x22; 32]),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let msg = message();
+        let bytes = msg.to_bytes().unwrap();
+        assert_eq!(bytes.len(), msg.serialized_length());
+        let (decoded, rem) = RollbackMessage::from_bytes(&bytes).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(decoded, msg);
+    }
+
+    /// blake2b-256 of: u32-length-prefixed "sentinelx_rollback" and "casper-test", 32 x 0x11,
+    /// U256 7 (`01 07`) and 32 x 0x22, prefixed and hex encoded for the wallet
+    #[test]
+    fn fixed_vector() {
+        assert_eq!(
+            message().signed_bytes().unwrap(),
+            b"Casper Message:\nccb84167c2a81d49b2348b09f645e14bb51adccb37fa5b3fe1b94e113e77f43a"
+        );
+    }
+
+    #[test]
+    fn approval_signatures_do_not_authorize_rollbacks() {
+        let secret = SecretKey::ed25519_from_bytes([5; 32]).unwrap();
+        let public = PublicKey::from(&secret);
+        let approval = ApprovalMessage {
+            chain_name: String::from("casper-test"),
+            registry: [0x11; 32],
+            recovery_id: U256::from(7),
+            account: AccountHash::new([0x22; 32]),
+            targets: vec![],
+            nonce: 0,
+        };
+        let signature = crypto::sign(approval.signed_bytes().unwrap(), &secret, &public);
+        let rollback = message().signed_bytes().unwrap();
+        assert!(crypto::verify(rollback, &signature, &public).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Helpers shared by the session WASMs (`contract` feature).
+
+use alloc::format;
+
+use casper_contract::contract_api::{account, runtime};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{
+    account::{AccountHash, ActionType, Weight},
+    contracts::ContractHash,
+    ApiError, Key,
+};
+
+use crate::args::{AccountArg, ArgBytes, ACCOUNT_ARG_KEY, ACCOUNT_ARG_KIND_SUFFIX};
+
+/// Account hash of the account argument `name`, decoded as the type its `<name>_kind` argument
+/// names (a `Key` when absent), reverting with `error` unless it refers to an account.
+pub fn account_arg(name: &str, error: impl Into<ApiError>) -> AccountHash {
+    let kind: u8 = runtime::try_get_named_arg(&format!("{}{}", name, ACCOUNT_ARG_KIND_SUFFIX))
+        .unwrap_or(ACCOUNT_ARG_KEY);
+    let ArgBytes(bytes) = runtime::get_named_arg(name);
+    match AccountArg::decode(kind, &bytes).ok().and_then(|arg| arg.account_hash()) {
+        Some(account_hash) => account_hash,
+        None => runtime::revert(error),
+    }
+}
+
+/// Contract hash of the recovery_registry passed as the `registry` argument, reverting with
+/// `error` if the key is not a hash key.
+pub fn registry_contract_hash(registry: Key, error: impl Into<ApiError>) -> ContractHash {
+    match registry.into_hash_addr() {
+        Some(addr) => ContractHash::new(addr),
+        None => runtime::revert(error),
+    }
+}
+
+/// Set the account's deployment and key management thresholds, reverting with `error` if
+/// they cannot be applied.
+///
+/// Key management must never drop below deployment, so key management goes first when the
+/// thresholds are raised and last when they are lowered.
+pub fn set_action_thresholds(
+    deployment: u8,
+    key_management: u8,
+    error: impl Into<ApiError> + Copy,
+) {
+    if account::set_action_threshold(ActionType::KeyManagement, Weight::new(key_management))
+        .is_ok()
+    {
+        account::set_action_threshold(ActionType::Deployment, Weight::new(deployment))
+            .unwrap_or_revert_with(error);
+    } else {
+        account::set_action_threshold(ActionType::Deployment, Weight::new(deployment))
+            .unwrap_or_revert_with(error);
+        account::set_action_threshold(ActionType::KeyManagement, Weight::new(key_management))
+            .unwrap_or_revert_with(error);
+    }
+}